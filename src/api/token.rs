@@ -1,4 +1,6 @@
 use crate::models::authentication::Authentication;
+use crate::utils::jwt::{self, JwtVerifyError};
+use crate::utils::security_stamp;
 use crate::utils::time::{deserialize_offset_date_time, serialize_offset_date_time};
 use crate::{find_one_resource_where_fields, models::authentication::AuthenticationError};
 use rocket::{
@@ -47,9 +49,41 @@ impl VerifiedToken {
         }
     }
 
-    /// Attempts to create a VerifiedToken from a RawToken by validating it against the database
-    /// Returns an error if the token is invalid or expired
+    /// Attempts to create a VerifiedToken from a RawToken.
+    ///
+    /// First tries the stateless path: if `raw_token` is a well-formed, correctly
+    /// signed JWT (see `crate::utils::jwt`), its embedded `stamp` claim is compared
+    /// against the user's current `security_stamp` — a mismatch means the user
+    /// rotated their stamp (password or 2FA change) since this token was issued,
+    /// so it's rejected as `InvalidToken` even though the signature still checks
+    /// out. Otherwise the `VerifiedToken` is built directly from the `sub`/`exp`
+    /// claims, with no `Authentication` table round-trip. Any other string —
+    /// including the opaque, database-backed tokens issued before this mode existed —
+    /// falls back to validating against the `Authentication` table as before.
+    /// Returns an error if the token is invalid or expired either way.
     pub async fn from_raw(raw_token: RawToken) -> Result<Self, AuthenticationError> {
+        match jwt::verify(&raw_token.value) {
+            Ok(claims) => {
+                let current_stamp = security_stamp::get_security_stamp(&claims.sub)
+                    .await
+                    .map_err(|_| AuthenticationError::InvalidToken)?;
+                if current_stamp.as_deref() != Some(claims.stamp.as_str()) {
+                    return Err(AuthenticationError::InvalidToken);
+                }
+
+                return Ok(Self::new(
+                    raw_token.value,
+                    claims.sub,
+                    Some(
+                        OffsetDateTime::from_unix_timestamp(claims.exp)
+                            .map_err(|_| AuthenticationError::InvalidToken)?,
+                    ),
+                ));
+            }
+            Err(JwtVerifyError::Expired) => return Err(AuthenticationError::TokenExpired),
+            Err(JwtVerifyError::Malformed) => {}
+        }
+
         let params = vec![("token", &raw_token.value)];
         let authentication = match find_one_resource_where_fields!(Authentication, params).await {
             Ok(authentication) => authentication,
@@ -113,3 +147,27 @@ pub async fn validate_token(token: RawToken) -> Result<VerifiedToken, Authentica
         }
     }
 }
+
+/// The `User-Agent` header and client IP for the current request, captured
+/// at session issuance so `api::authentications::list_sessions` can display
+/// which device/location each `Authentication` row belongs to.
+#[derive(Debug, Clone)]
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ClientInfo {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> rocket::request::Outcome<Self, Self::Error> {
+        Outcome::Success(ClientInfo {
+            user_agent: request
+                .headers()
+                .get_one("User-Agent")
+                .map(|value| value.to_string()),
+            ip: request.client_ip().map(|ip| ip.to_string()),
+        })
+    }
+}
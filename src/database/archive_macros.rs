@@ -0,0 +1,221 @@
+/// Macros for soft-deleting (archiving) and restoring database resources by ID.
+///
+/// # Description
+/// `delete_resource_where_fields!` already archives instead of hard-deleting when a
+/// resource is archivable, but it has no counterpart for explicitly archiving or
+/// restoring a specific resource outside of a delete — e.g. an admin "archive this
+/// record" action, or undoing one. These macros fill that gap:
+///
+/// - `archive_resource!($resource, $id)` sets `archived_at` to the current timestamp
+/// - `restore_resource!($resource, $id)` clears `archived_at` back to `NULL`
+///
+/// Both require `<$resource as DatabaseResource>::is_archivable()` to be `true` and
+/// return an error immediately otherwise, since setting `archived_at` on a resource
+/// that doesn't have the column would simply fail at the database.
+///
+/// # Returns
+/// * `Result<$resource, DatabaseError>` - the resource after the archive/restore, fetched via
+///   `RETURNING *`
+///
+/// # Example
+/// ```rust
+/// let archived_store = archive_resource!(Store, store_id).await?;
+/// let restored_store = restore_resource!(Store, store_id).await?;
+/// ```
+#[macro_export]
+macro_rules! archive_resource {
+    ($resource:ty, $id:expr) => {{
+        use crate::database::{connection::get_connection, traits::DatabaseResource};
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+        use time::OffsetDateTime;
+
+        async {
+            if !<$resource as DatabaseResource>::is_archivable() {
+                return Err(DatabaseError::NotArchivable(stringify!($resource).to_string()));
+            }
+
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+            let archived_at = OffsetDateTime::now_utc();
+
+            let query = format!(
+                "UPDATE {} SET archived_at = $1 WHERE id = $2 RETURNING *",
+                resource_name
+            );
+            let row = sqlx::query(&query)
+                .bind(archived_at)
+                .bind(&$id)
+                .fetch_one(&pool)
+                .await
+                .map_err(DatabaseError::from)?;
+
+            Ok(<$resource as DatabaseResource>::from_row(&row)?)
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! restore_resource {
+    ($resource:ty, $id:expr) => {{
+        use crate::database::{connection::get_connection, traits::DatabaseResource};
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            if !<$resource as DatabaseResource>::is_archivable() {
+                return Err(DatabaseError::NotArchivable(stringify!($resource).to_string()));
+            }
+
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let query = format!(
+                "UPDATE {} SET archived_at = NULL WHERE id = $1 RETURNING *",
+                resource_name
+            );
+            let row = sqlx::query(&query)
+                .bind(&$id)
+                .fetch_one(&pool)
+                .await
+                .map_err(DatabaseError::from)?;
+
+            Ok(<$resource as DatabaseResource>::from_row(&row)?)
+        }
+    }};
+}
+
+/// Archives every resource matching a flat list of `field = value` pairs,
+/// rather than a single resource by ID like `archive_resource!` above — the
+/// `_where_fields!` counterpart to `delete_resource_where_fields!`, using the
+/// same safe parameter binding. Returns the number of rows archived rather
+/// than the rows themselves, since a bulk archive can touch far more rows
+/// than a caller would want fetched back.
+///
+/// Requires `<$resource as DatabaseResource>::is_archivable()` to be `true`,
+/// same as `archive_resource!`/`restore_resource!`.
+///
+/// # Returns
+/// * `Result<u64, DatabaseError>` - the number of rows archived
+///
+/// # Example
+/// ```rust
+/// let conditions = vec![("user_id".to_string(), DatabaseValue::String(user_id))];
+/// let archived_count = archive_resource_where_fields!(OauthToken, conditions).await?;
+/// ```
+#[macro_export]
+macro_rules! archive_resource_where_fields {
+    ($resource:ty, $params:expr) => {{
+        use crate::database::connection::get_connection;
+        use crate::database::traits::DatabaseResource;
+        use crate::database::values::DatabaseValue;
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+        use time::OffsetDateTime;
+
+        async {
+            if !<$resource as DatabaseResource>::is_archivable() {
+                return Err(DatabaseError::NotArchivable(stringify!($resource).to_string()));
+            }
+
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+            let archived_at = OffsetDateTime::now_utc();
+
+            let params = $params.clone();
+            let fields: Vec<String> = params.iter().map(|field| field.0.to_string()).collect();
+            let values: Vec<DatabaseValue> = params.iter().map(|field| field.1.clone()).collect();
+
+            let mut query = format!("UPDATE {} SET archived_at = $1 WHERE ", resource_name);
+            for (i, field) in fields.iter().enumerate() {
+                query.push_str(&format!("{} = ${}", field, i + 2));
+                if i < fields.len() - 1 {
+                    query.push_str(" AND ");
+                }
+            }
+
+            let mut query = sqlx::query(&query).bind(archived_at);
+            for value in values.iter() {
+                query = query.bind(value);
+            }
+
+            query
+                .execute(&pool)
+                .await
+                .map(|result| result.rows_affected())
+                .map_err(DatabaseError::from)
+        }
+    }};
+}
+
+/// Restores every resource matching a flat list of `field = value` pairs,
+/// rather than a single resource by ID like `restore_resource!` above —
+/// the `_where_fields!` counterpart to `delete_resource_where_fields!`, using
+/// the same safe parameter binding.
+///
+/// Requires `<$resource as DatabaseResource>::is_archivable()` to be `true`,
+/// same as `archive_resource!`/`restore_resource!`.
+///
+/// # Example
+/// ```rust
+/// let conditions = vec![("user_id".to_string(), DatabaseValue::String(user_id))];
+/// restore_resource_where_fields!(OauthToken, conditions).await?;
+/// ```
+#[macro_export]
+macro_rules! restore_resource_where_fields {
+    ($resource:ty, $params:expr) => {{
+        use crate::database::connection::get_connection;
+        use crate::database::traits::DatabaseResource;
+        use crate::database::values::DatabaseValue;
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            if !<$resource as DatabaseResource>::is_archivable() {
+                return Err(DatabaseError::NotArchivable(stringify!($resource).to_string()));
+            }
+
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let params = $params.clone();
+            let fields: Vec<String> = params.iter().map(|field| field.0.to_string()).collect();
+            let values: Vec<DatabaseValue> = params.iter().map(|field| field.1.clone()).collect();
+
+            let mut query = format!("UPDATE {} SET archived_at = NULL WHERE ", resource_name);
+            for (i, field) in fields.iter().enumerate() {
+                query.push_str(&format!("{} = ${}", field, i + 1));
+                if i < fields.len() - 1 {
+                    query.push_str(" AND ");
+                }
+            }
+
+            let mut query = sqlx::query(&query);
+            for value in values.iter() {
+                query = query.bind(value);
+            }
+
+            query.execute(&pool).await.map(|_| ()).map_err(DatabaseError::from)
+        }
+    }};
+}
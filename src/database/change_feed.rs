@@ -0,0 +1,100 @@
+//! Process-wide change feed for the mutating resource macros.
+//!
+//! `insert_resource!`, `update_resource!`/`update_resource_where_condition!`,
+//! and `delete_resource_where_fields!`/`delete_resource_where_condition!` each
+//! publish a [`ChangeEvent`] here after a successful write, via [`publish`].
+//! `websockets::handle_connection` subscribes to the same feed with
+//! [`subscribe`] and forwards matching events to its client, turning the
+//! otherwise-inert `/ws` route into a live alerting channel.
+//!
+//! The channel is a single global `broadcast::Sender`, lazily created on
+//! first use, rather than something threaded through every macro call site —
+//! mirroring how `utils::websocket`'s per-user registry is reached from
+//! anywhere without a request context. [`publish`] is a no-op when nobody is
+//! subscribed (a `broadcast::Sender::send` with no receivers just returns an
+//! error we discard), so non-server uses of the macros — tests, one-off
+//! scripts — are unaffected.
+
+use crate::database::values::DatabaseValue;
+use serde::Serialize;
+use std::sync::OnceLock;
+use time::OffsetDateTime;
+use tokio::sync::broadcast;
+
+/// How many unread events a slow subscriber can fall behind by before it
+/// starts missing them (see `broadcast::error::RecvError::Lagged`).
+const CHANNEL_CAPACITY: usize = 256;
+
+/// What happened to a row: a plain insert, a field update, a soft-delete
+/// (`archived_at` set instead of a row removal), or a hard delete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOp {
+    Created,
+    Updated,
+    Archived,
+    Deleted,
+}
+
+/// A single row mutation performed through the resource macros.
+///
+/// `user_id` is populated whenever the mutated row itself has a `user_id`
+/// field (most resources in this crate do), so a client can subscribe to
+/// just its own changes without the server needing a separate notion of
+/// "ownership" per resource type.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeEvent {
+    /// The resource type name, e.g. `"UserBackupCode"`.
+    pub resource: String,
+    /// The table the row lives in, e.g. `"user_backup_codes"`.
+    pub table: String,
+    pub op: ChangeOp,
+    pub id: String,
+    pub changed_fields: Vec<String>,
+    pub user_id: Option<String>,
+    /// Unix seconds.
+    pub timestamp: i64,
+}
+
+static SENDER: OnceLock<broadcast::Sender<ChangeEvent>> = OnceLock::new();
+
+fn sender() -> &'static broadcast::Sender<ChangeEvent> {
+    SENDER.get_or_init(|| broadcast::channel(CHANNEL_CAPACITY).0)
+}
+
+/// Publishes a change event to every current subscriber.
+pub fn publish(event: ChangeEvent) {
+    let _ = sender().send(event);
+}
+
+/// Subscribes to the global change feed, e.g. from a WebSocket handler.
+pub fn subscribe() -> broadcast::Receiver<ChangeEvent> {
+    sender().subscribe()
+}
+
+/// Renders a textual `DatabaseValue` back to a plain `String`, for values the
+/// macros already know are text columns (e.g. `user_id`) — unlike `Display`,
+/// which exists for debugging and includes the enum's variant name.
+pub fn value_as_string(value: &DatabaseValue) -> Option<String> {
+    match value {
+        DatabaseValue::Str(s) => Some(s.to_string()),
+        DatabaseValue::String(s) | DatabaseValue::Text(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+/// Finds `user_id` within a rendered `(field, value)` pair and renders it as
+/// a string, for the mutating macros to populate [`ChangeEvent::user_id`]
+/// without each of them duplicating the lookup.
+pub fn find_user_id(fields: &[String], values: &[DatabaseValue]) -> Option<String> {
+    fields
+        .iter()
+        .position(|field| field == "user_id")
+        .and_then(|idx| value_as_string(&values[idx]))
+}
+
+/// Unix-second timestamp for a freshly published event.
+pub fn now() -> i64 {
+    OffsetDateTime::now_utc().unix_timestamp()
+}
@@ -0,0 +1,156 @@
+//! Database connection pool management.
+//!
+//! Exposes a single lazily-initialized `sqlx` Postgres pool via
+//! `get_connection()`, the way every `find_*`/`insert_resource!`/etc. macro
+//! expects it. By default the pool is built from `ConnectionOptions::default()`
+//! and the `DATABASE_URL` environment variable the first time `get_connection`
+//! is called, so existing call sites keep working unchanged.
+//!
+//! Applications that want to tune pool sizing or run per-connection setup
+//! (SQLite `PRAGMA foreign_keys`/`busy_timeout`, Postgres `SET` statements,
+//! etc.) should call `init_connection` once at startup, before the first
+//! `get_connection` call, with a custom `ConnectionOptions`.
+
+use crate::error::DatabaseError;
+use sqlx::postgres::{PgPoolOptions, PgPool};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+/// Tuning knobs for the shared connection pool.
+///
+/// Mirrors the pool-builder pattern used by most sqlx-based services: a
+/// min/max pool size, acquire/idle timeouts, and a list of SQL statements run
+/// against every freshly-opened connection before it's handed back to a
+/// caller (`after_connect_statements`).
+#[derive(Debug, Clone)]
+pub struct ConnectionOptions {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout: Duration,
+    pub idle_timeout: Option<Duration>,
+    /// Statements run on every new connection, e.g.
+    /// `"SET statement_timeout = 5000"` or `"PRAGMA foreign_keys = ON"`.
+    pub after_connect_statements: Vec<String>,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout: Duration::from_secs(30),
+            idle_timeout: Some(Duration::from_secs(10 * 60)),
+            after_connect_statements: Vec::new(),
+        }
+    }
+}
+
+impl ConnectionOptions {
+    pub fn builder() -> ConnectionOptionsBuilder {
+        ConnectionOptionsBuilder::default()
+    }
+}
+
+/// Builder for `ConnectionOptions`, so callers can override only the knobs
+/// they care about and fall back to `ConnectionOptions::default()` for the rest.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionOptionsBuilder {
+    options: ConnectionOptionsOverrides,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ConnectionOptionsOverrides {
+    max_connections: Option<u32>,
+    min_connections: Option<u32>,
+    acquire_timeout: Option<Duration>,
+    idle_timeout: Option<Option<Duration>>,
+    after_connect_statements: Vec<String>,
+}
+
+impl ConnectionOptionsBuilder {
+    pub fn max_connections(mut self, max_connections: u32) -> Self {
+        self.options.max_connections = Some(max_connections);
+        self
+    }
+
+    pub fn min_connections(mut self, min_connections: u32) -> Self {
+        self.options.min_connections = Some(min_connections);
+        self
+    }
+
+    pub fn acquire_timeout(mut self, acquire_timeout: Duration) -> Self {
+        self.options.acquire_timeout = Some(acquire_timeout);
+        self
+    }
+
+    pub fn idle_timeout(mut self, idle_timeout: Option<Duration>) -> Self {
+        self.options.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Adds a statement to run on every new connection (e.g. a `PRAGMA` or `SET`).
+    /// Statements run in the order they were added.
+    pub fn after_connect(mut self, statement: impl Into<String>) -> Self {
+        self.options.after_connect_statements.push(statement.into());
+        self
+    }
+
+    pub fn build(self) -> ConnectionOptions {
+        let defaults = ConnectionOptions::default();
+        ConnectionOptions {
+            max_connections: self.options.max_connections.unwrap_or(defaults.max_connections),
+            min_connections: self.options.min_connections.unwrap_or(defaults.min_connections),
+            acquire_timeout: self.options.acquire_timeout.unwrap_or(defaults.acquire_timeout),
+            idle_timeout: self.options.idle_timeout.unwrap_or(defaults.idle_timeout),
+            after_connect_statements: self.options.after_connect_statements,
+        }
+    }
+}
+
+static POOL: OnceCell<PgPool> = OnceCell::const_new();
+
+/// Builds the shared connection pool from `database_url` and `options`.
+///
+/// Must be called at most once, before the first `get_connection` call.
+/// Returns an error if the pool was already initialized or the connection
+/// to `database_url` could not be established.
+pub async fn init_connection(
+    database_url: &str,
+    options: ConnectionOptions,
+) -> Result<(), DatabaseError> {
+    let statements = options.after_connect_statements.clone();
+    let pool_options = PgPoolOptions::new()
+        .max_connections(options.max_connections)
+        .min_connections(options.min_connections)
+        .acquire_timeout(options.acquire_timeout)
+        .idle_timeout(options.idle_timeout)
+        .after_connect(move |conn, _meta| {
+            let statements = statements.clone();
+            Box::pin(async move {
+                for statement in &statements {
+                    sqlx::query(statement).execute(&mut *conn).await?;
+                }
+                Ok(())
+            })
+        });
+
+    let pool = pool_options.connect(database_url).await?;
+    POOL.set(pool).map_err(|_| DatabaseError::PoolAlreadyInitialized)
+}
+
+/// Returns the shared connection pool, lazily initializing it with
+/// `ConnectionOptions::default()` and the `DATABASE_URL` environment
+/// variable if `init_connection` was never called.
+pub async fn get_connection() -> PgPool {
+    POOL.get_or_init(|| async {
+        let database_url =
+            std::env::var("DATABASE_URL").expect("DATABASE_URL must be set if init_connection was not called");
+        PgPoolOptions::new()
+            .max_connections(ConnectionOptions::default().max_connections)
+            .connect(&database_url)
+            .await
+            .expect("failed to connect to the database")
+    })
+    .await
+    .clone()
+}
@@ -18,7 +18,7 @@
 ///   - Second element is the value to match against
 ///
 /// # Returns
-/// * `Result<(), anyhow::Error>` - Ok(()) on success, Error on failure
+/// * `Result<(), DatabaseError>` - Ok(()) on success, Error on failure
 ///
 /// # Implementation Details
 /// For archivable resources (where `is_archivable()` returns true):
@@ -51,11 +51,12 @@
 #[macro_export]
 macro_rules! delete_resource_where_fields {
     ($resource:ty, $params:expr) => {{
+        use crate::database::change_feed::{self, ChangeEvent, ChangeOp};
         use crate::database::connection::get_connection;
         use crate::database::traits::DatabaseResource;
         use crate::database::values::DatabaseValue;
+        use crate::error::DatabaseError;
         use crate::utils::strings::camel_to_snake_case;
-        use anyhow::anyhow;
         use pluralizer::pluralize;
         use time::OffsetDateTime;
 
@@ -91,6 +92,7 @@ macro_rules! delete_resource_where_fields {
                     query.push_str(" AND ");
                 }
             }
+            query.push_str(" RETURNING id");
 
             let mut query = sqlx::query(&query);
             for (_, value) in values.iter().enumerate() {
@@ -100,9 +102,215 @@ macro_rules! delete_resource_where_fields {
                 query = query.bind(archived_at);
             }
 
-            match query.execute(&pool).await {
-                Ok(_) => Ok(()),
-                Err(e) => Err(anyhow!(e)),
+            let op = if <$resource as DatabaseResource>::is_archivable() {
+                ChangeOp::Archived
+            } else {
+                ChangeOp::Deleted
+            };
+            let user_id = change_feed::find_user_id(&fields, &values);
+
+            match query.fetch_all(&pool).await {
+                Ok(rows) => {
+                    for row in &rows {
+                        let id: String = match sqlx::Row::try_get(row, "id") {
+                            Ok(id) => id,
+                            Err(e) => return Err(DatabaseError::from(e)),
+                        };
+                        change_feed::publish(ChangeEvent {
+                            resource: stringify!($resource).to_string(),
+                            table: resource_name.clone(),
+                            op,
+                            id,
+                            changed_fields: fields.clone(),
+                            user_id: user_id.clone(),
+                            timestamp: change_feed::now(),
+                        });
+                    }
+                    Ok(())
+                }
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+    // Same as the two-argument form, but executed against a request-scoped
+    // `DbConn` transaction (see `crate::database::transaction`) instead of a
+    // fresh pool connection.
+    ($resource:ty, $params:expr, $conn:expr) => {{
+        use crate::database::change_feed::{self, ChangeEvent, ChangeOp};
+        use crate::database::traits::DatabaseResource;
+        use crate::database::values::DatabaseValue;
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+        use time::OffsetDateTime;
+
+        async {
+            let archived_at = OffsetDateTime::now_utc();
+
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+
+            let params = $params.clone();
+
+            let fields: Vec<String> = params.iter().map(|field| field.0.to_string()).collect();
+            let values: Vec<DatabaseValue> = params.iter().map(|field| field.1.clone()).collect();
+
+            let mut query: String;
+            if <$resource as DatabaseResource>::is_archivable() {
+                query = format!(
+                    "UPDATE {} SET archived_at = CAST(${} AS TIMESTAMP) WHERE ",
+                    resource_name,
+                    fields.len() + 1
+                );
+            } else {
+                query = format!("DELETE FROM {} WHERE ", resource_name);
+            }
+
+            for (i, field) in fields.iter().enumerate() {
+                query.push_str(&format!("{} = ${}", field, i + 1));
+                if i < fields.len() - 1 {
+                    query.push_str(" AND ");
+                }
+            }
+            query.push_str(" RETURNING id");
+
+            let mut query = sqlx::query(&query);
+            for (_, value) in values.iter().enumerate() {
+                query = query.bind(value);
+            }
+            if <$resource as DatabaseResource>::is_archivable() {
+                query = query.bind(archived_at);
+            }
+
+            let op = if <$resource as DatabaseResource>::is_archivable() {
+                ChangeOp::Archived
+            } else {
+                ChangeOp::Deleted
+            };
+            let user_id = change_feed::find_user_id(&fields, &values);
+
+            let mut guard = $conn.lock().await;
+            let tx = guard
+                .as_mut()
+                .expect("DbConn used after its transaction was finalized");
+            match query.fetch_all(&mut *tx).await {
+                Ok(rows) => {
+                    for row in &rows {
+                        let id: String = match sqlx::Row::try_get(row, "id") {
+                            Ok(id) => id,
+                            Err(e) => return Err(DatabaseError::from(e)),
+                        };
+                        change_feed::publish(ChangeEvent {
+                            resource: stringify!($resource).to_string(),
+                            table: resource_name.clone(),
+                            op,
+                            id,
+                            changed_fields: fields.clone(),
+                            user_id: user_id.clone(),
+                            timestamp: change_feed::now(),
+                        });
+                    }
+                    Ok(())
+                }
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+}
+
+/// Deletes or archives resources matching an arbitrary `Condition` tree,
+/// rather than a flat list of `field = value` pairs ANDed together.
+///
+/// See `database::predicate` for the available leaf operators (`Predicate::Lt`,
+/// `In`, `Like`, ...) and grouping (`Condition::And`/`Condition::Or`) — this is
+/// what makes "archive sessions where `expires_at < now`" or "delete tokens
+/// where `token_type IN (...)`" possible, which `delete_resource_where_fields!`'s
+/// flat equality list cannot express. Archiving vs. hard-deleting is decided
+/// the same way as `delete_resource_where_fields!`, based on
+/// `<$resource as DatabaseResource>::is_archivable()`.
+///
+/// # Example
+/// ```rust
+/// use crate::database::predicate::{Condition, Predicate};
+///
+/// delete_resource_where_condition!(
+///     Authentication,
+///     Condition::pred("expires_at", Predicate::Lt(DatabaseValue::DateTime(now)))
+/// ).await?;
+/// ```
+#[macro_export]
+macro_rules! delete_resource_where_condition {
+    ($resource:ty, $condition:expr) => {{
+        use crate::database::{
+            change_feed::{self, ChangeEvent, ChangeOp},
+            connection::get_connection, traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+        use time::OffsetDateTime;
+
+        async {
+            let archived_at = OffsetDateTime::now_utc();
+
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let (where_clause, bind_values, next_index) = $condition.render(1);
+
+            let query_str = if <$resource as DatabaseResource>::is_archivable() {
+                format!(
+                    "UPDATE {} SET archived_at = CAST(${} AS TIMESTAMP) WHERE {} RETURNING id",
+                    resource_name, next_index, where_clause
+                )
+            } else {
+                format!(
+                    "DELETE FROM {} WHERE {} RETURNING id",
+                    resource_name, where_clause
+                )
+            };
+
+            let mut query = sqlx::query(&query_str);
+            for value in bind_values.iter() {
+                query = query.bind(value);
+            }
+            if <$resource as DatabaseResource>::is_archivable() {
+                query = query.bind(archived_at);
+            }
+
+            let op = if <$resource as DatabaseResource>::is_archivable() {
+                ChangeOp::Archived
+            } else {
+                ChangeOp::Deleted
+            };
+
+            match query.fetch_all(&pool).await {
+                Ok(rows) => {
+                    for row in &rows {
+                        let id: String = match sqlx::Row::try_get(row, "id") {
+                            Ok(id) => id,
+                            Err(e) => return Err(DatabaseError::from(e)),
+                        };
+                        change_feed::publish(ChangeEvent {
+                            resource: stringify!($resource).to_string(),
+                            table: resource_name.clone(),
+                            op,
+                            id,
+                            changed_fields: Vec::new(),
+                            user_id: None,
+                            timestamp: change_feed::now(),
+                        });
+                    }
+                    Ok(())
+                }
+                Err(e) => Err(DatabaseError::from(e)),
             }
         }
     }};
@@ -27,6 +27,10 @@
 ///
 /// * `$resource` - The type that implements the `DatabaseResource` trait
 /// * `$params` - A Vec of tuples containing (field_name, DatabaseValue) pairs
+/// * `$conn` (optional) - A `&DbConn` to insert within its request-scoped
+///   transaction (see `crate::database::transaction`) instead of a fresh pool
+///   connection, so it rolls back together with everything else run against
+///   that transaction
 ///
 /// # Type Requirements
 ///
@@ -123,8 +127,10 @@ macro_rules! insert_resource {
         // - Time handling (ISO8601 formatting, UTC timestamps)
         // - UUID generation (v4 UUIDs for unique identifiers)
         use crate::database::{
+            change_feed::{self, ChangeEvent, ChangeOp},
             connection::get_connection, traits::DatabaseResource, values::DatabaseValue,
         };
+        use crate::error::DatabaseError;
         use crate::utils::strings::camel_to_snake_case;
         use pluralizer::pluralize;
         use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime};
@@ -310,12 +316,552 @@ macro_rules! insert_resource {
             // - On error: Return the database error directly
             // The ? operator propagates any conversion errors
             match query.fetch_one(&pool).await {
-                Ok(row) => Ok(<$resource as DatabaseResource>::from_row(&row)?),
+                Ok(row) => {
+                    let resource = <$resource as DatabaseResource>::from_row(&row)?;
+                    let user_id = change_feed::find_user_id(&fields, &values);
+                    change_feed::publish(ChangeEvent {
+                        resource: stringify!($resource).to_string(),
+                        table: resource_name,
+                        op: ChangeOp::Created,
+                        id,
+                        changed_fields: fields,
+                        user_id,
+                        timestamp: change_feed::now(),
+                    });
+                    Ok(resource)
+                }
+                Err(e) => {
+                    println!("Error fetching row: {:?}", e);
+                    Err(DatabaseError::from(e))
+                }
+            }
+        }
+    }};
+    // Same as the two-argument form, but executed against a request-scoped
+    // `DbConn` transaction (see `crate::database::transaction`) instead of a
+    // fresh pool connection, so it rolls back together with everything else
+    // run against that transaction.
+    ($resource:ty, $params:expr, $conn:expr) => {{
+        use crate::database::{
+            change_feed::{self, ChangeEvent, ChangeOp},
+            traits::DatabaseResource, values::DatabaseValue,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+        use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime};
+        use uuid::Uuid;
+
+        let input_params = $params.clone();
+        async {
+            let id = Uuid::new_v4().to_string();
+            let created_at = OffsetDateTime::now_utc().format(&Iso8601::DEFAULT).unwrap();
+            let updated_at = created_at.clone();
+            let expires_at = (OffsetDateTime::now_utc() + Duration::days(30))
+                .format(&Iso8601::DEFAULT)
+                .unwrap();
+
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+
+            let mut params: Vec<(String, DatabaseValue)> = Vec::new();
+            for (field, value) in input_params.into_iter() {
+                params.push((field.to_string(), value.clone()))
+            }
+
+            if <$resource as DatabaseResource>::has_id() {
+                params.push(("id".to_string(), DatabaseValue::String(id.clone())));
+            }
+
+            if <$resource as DatabaseResource>::is_creatable() {
+                if let Some(idx) = params
+                    .iter()
+                    .position(|(field, _)| field.contains("created_at"))
+                {
+                    params[idx] = (
+                        "created_at".to_string(),
+                        DatabaseValue::DateTime(created_at),
+                    );
+                } else {
+                    params.push((
+                        "created_at".to_string(),
+                        DatabaseValue::DateTime(created_at),
+                    ));
+                }
+            }
+
+            if <$resource as DatabaseResource>::is_updatable() {
+                if let Some(idx) = params
+                    .iter()
+                    .position(|(field, _)| field.contains("updated_at"))
+                {
+                    params[idx] = (
+                        "updated_at".to_string(),
+                        DatabaseValue::DateTime(updated_at),
+                    );
+                } else {
+                    params.push((
+                        "updated_at".to_string(),
+                        DatabaseValue::DateTime(updated_at),
+                    ));
+                }
+            }
+
+            if <$resource as DatabaseResource>::is_expirable() {
+                if let Some(idx) = params
+                    .iter()
+                    .position(|(field, _)| field.contains("expires_at"))
+                {
+                    params[idx] = (
+                        "expires_at".to_string(),
+                        DatabaseValue::DateTime(expires_at),
+                    );
+                } else {
+                    params.push((
+                        "expires_at".to_string(),
+                        DatabaseValue::DateTime(expires_at),
+                    ));
+                }
+            }
+
+            let fields: Vec<String> = params.iter().map(|(field, _)| field.clone()).collect();
+            let values: Vec<DatabaseValue> =
+                params.iter().map(|(_, value)| (*value).clone()).collect();
+
+            let mut query = format!("INSERT INTO {} (", resource_name);
+            for (i, field) in fields.iter().enumerate() {
+                query.push_str(field);
+                if i < fields.len() - 1 {
+                    query.push_str(", ");
+                }
+            }
+
+            query.push_str(") VALUES (");
+            for (i, value) in values.iter().enumerate() {
+                match value {
+                    DatabaseValue::None => {
+                        query.push_str("NULL");
+                    }
+                    DatabaseValue::Str(_) | DatabaseValue::String(_) => {
+                        query.push_str(&format!("Cast(${} AS VARCHAR)", i + 1));
+                    }
+                    DatabaseValue::Text(_) => {
+                        query.push_str(&format!("Cast(${} AS TEXT)", i + 1));
+                    }
+                    DatabaseValue::DateTime(_) => {
+                        query.push_str(&format!("CAST(${} AS TIMESTAMP)", i + 1));
+                    }
+                    DatabaseValue::Int(_) => {
+                        query.push_str(&format!("CAST(${} AS INTEGER)", i + 1));
+                    }
+                    DatabaseValue::Int64(_) => {
+                        query.push_str(&format!("CAST(${} AS BIGINT)", i + 1));
+                    }
+                    DatabaseValue::Float(_) => {
+                        query.push_str(&format!("CAST(${} AS FLOAT)", i + 1));
+                    }
+                    DatabaseValue::Boolean(_) => {
+                        query.push_str(&format!("CAST(${} AS BOOLEAN)", i + 1));
+                    }
+                }
+                if i < values.len() - 1 {
+                    query.push_str(", ");
+                }
+            }
+            query.push_str(") RETURNING *");
+
+            let mut query = sqlx::query(&query);
+            for (_, value) in values.iter().enumerate() {
+                query = query.bind(value);
+            }
+
+            let mut guard = $conn.lock().await;
+            let tx = guard
+                .as_mut()
+                .expect("DbConn used after its transaction was finalized");
+            match query.fetch_one(&mut *tx).await {
+                Ok(row) => {
+                    let resource = <$resource as DatabaseResource>::from_row(&row)?;
+                    let user_id = change_feed::find_user_id(&fields, &values);
+                    change_feed::publish(ChangeEvent {
+                        resource: stringify!($resource).to_string(),
+                        table: resource_name,
+                        op: ChangeOp::Created,
+                        id,
+                        changed_fields: fields,
+                        user_id,
+                        timestamp: change_feed::now(),
+                    });
+                    Ok(resource)
+                }
                 Err(e) => {
                     println!("Error fetching row: {:?}", e);
-                    Err(e)
+                    Err(DatabaseError::from(e))
                 }
             }
         }
     }};
 }
+
+/// Inserts a resource, or updates an existing row on a unique-constraint conflict.
+///
+/// # Overview
+///
+/// Builds exactly the same `INSERT INTO ... VALUES (...)` statement as
+/// `insert_resource!` (same automatic `id`/`created_at`/`updated_at`/`expires_at`
+/// handling and type casting), then appends an `ON CONFLICT` clause so a
+/// unique-constraint violation updates the row instead of erroring — useful
+/// for idempotent writes like upserting a session token or a per-user setting.
+///
+/// # Arguments
+/// * `$resource` - The type that implements the `DatabaseResource` trait
+/// * `$params` - A Vec of tuples containing (field_name, DatabaseValue) pairs
+/// * `$conflict_columns` - A `Vec<&str>` of the columns in the unique
+///   constraint to detect the conflict on, e.g. `vec!["user_id", "provider"]`
+/// * `$update_columns` - A `Vec<&str>` of columns to refresh with
+///   `EXCLUDED.col` when a conflict occurs. If this resource `is_updatable()`,
+///   `updated_at` is refreshed on conflict automatically whether or not it's
+///   listed here. An empty `$update_columns` (and a non-updatable resource)
+///   generates `ON CONFLICT (...) DO NOTHING`, in which case a conflict
+///   returns no row — callers should be prepared for `fetch_one`'s
+///   `RowNotFound` error in that case, as with any `DO NOTHING` upsert.
+///
+/// # Example
+/// ```rust
+/// let params = vec![
+///     ("user_id".to_string(), DatabaseValue::String(user_id)),
+///     ("provider".to_string(), DatabaseValue::String("google".to_string())),
+///     ("access_token".to_string(), DatabaseValue::String(access_token)),
+/// ];
+/// let token = upsert_resource!(
+///     OauthToken,
+///     params,
+///     vec!["user_id", "provider"],
+///     vec!["access_token"]
+/// ).await?;
+/// ```
+#[macro_export]
+macro_rules! upsert_resource {
+    ($resource:ty, $params:expr, $conflict_columns:expr, $update_columns:expr) => {{
+        use crate::database::{
+            connection::get_connection, traits::DatabaseResource, values::DatabaseValue,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+        use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime};
+        use uuid::Uuid;
+
+        let input_params = $params.clone();
+        async {
+            let id = Uuid::new_v4().to_string();
+            let created_at = OffsetDateTime::now_utc().format(&Iso8601::DEFAULT).unwrap();
+            let updated_at = created_at.clone();
+            let expires_at = (OffsetDateTime::now_utc() + Duration::days(30))
+                .format(&Iso8601::DEFAULT)
+                .unwrap();
+
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let mut params: Vec<(String, DatabaseValue)> = Vec::new();
+            for (field, value) in input_params.into_iter() {
+                params.push((field.to_string(), value.clone()))
+            }
+
+            if <$resource as DatabaseResource>::has_id() {
+                params.push(("id".to_string(), DatabaseValue::String(id.clone())));
+            }
+            if <$resource as DatabaseResource>::is_creatable() {
+                if let Some(idx) = params
+                    .iter()
+                    .position(|(field, _)| field.contains("created_at"))
+                {
+                    params[idx] = (
+                        "created_at".to_string(),
+                        DatabaseValue::DateTime(created_at),
+                    );
+                } else {
+                    params.push((
+                        "created_at".to_string(),
+                        DatabaseValue::DateTime(created_at),
+                    ));
+                }
+            }
+            if <$resource as DatabaseResource>::is_updatable() {
+                if let Some(idx) = params
+                    .iter()
+                    .position(|(field, _)| field.contains("updated_at"))
+                {
+                    params[idx] = (
+                        "updated_at".to_string(),
+                        DatabaseValue::DateTime(updated_at),
+                    );
+                } else {
+                    params.push((
+                        "updated_at".to_string(),
+                        DatabaseValue::DateTime(updated_at),
+                    ));
+                }
+            }
+            if <$resource as DatabaseResource>::is_expirable() {
+                if let Some(idx) = params
+                    .iter()
+                    .position(|(field, _)| field.contains("expires_at"))
+                {
+                    params[idx] = (
+                        "expires_at".to_string(),
+                        DatabaseValue::DateTime(expires_at),
+                    );
+                } else {
+                    params.push((
+                        "expires_at".to_string(),
+                        DatabaseValue::DateTime(expires_at),
+                    ));
+                }
+            }
+
+            let fields: Vec<String> = params.iter().map(|(field, _)| field.clone()).collect();
+            let values: Vec<DatabaseValue> =
+                params.iter().map(|(_, value)| (*value).clone()).collect();
+
+            let mut query = format!("INSERT INTO {} (", resource_name);
+            for (i, field) in fields.iter().enumerate() {
+                query.push_str(field);
+                if i < fields.len() - 1 {
+                    query.push_str(", ");
+                }
+            }
+
+            query.push_str(") VALUES (");
+            for (i, value) in values.iter().enumerate() {
+                match value {
+                    DatabaseValue::None => query.push_str("NULL"),
+                    DatabaseValue::Str(_) | DatabaseValue::String(_) => {
+                        query.push_str(&format!("CAST(${} AS VARCHAR)", i + 1))
+                    }
+                    DatabaseValue::Text(_) => {
+                        query.push_str(&format!("CAST(${} AS TEXT)", i + 1))
+                    }
+                    DatabaseValue::DateTime(_) => {
+                        query.push_str(&format!("CAST(${} AS TIMESTAMP)", i + 1))
+                    }
+                    DatabaseValue::Int(_) => {
+                        query.push_str(&format!("CAST(${} AS INTEGER)", i + 1))
+                    }
+                    DatabaseValue::Int64(_) => {
+                        query.push_str(&format!("CAST(${} AS BIGINT)", i + 1))
+                    }
+                    DatabaseValue::Float(_) => {
+                        query.push_str(&format!("CAST(${} AS FLOAT)", i + 1))
+                    }
+                    DatabaseValue::Boolean(_) => {
+                        query.push_str(&format!("CAST(${} AS BOOLEAN)", i + 1))
+                    }
+                }
+                if i < values.len() - 1 {
+                    query.push_str(", ");
+                }
+            }
+            query.push(')');
+
+            // Build the ON CONFLICT clause. `updated_at` is always refreshed
+            // on conflict for updatable resources, even if the caller didn't
+            // list it explicitly.
+            let conflict_columns: Vec<&str> = $conflict_columns;
+            let mut update_columns: Vec<&str> = $update_columns;
+            if <$resource as DatabaseResource>::is_updatable()
+                && !update_columns.contains(&"updated_at")
+            {
+                update_columns.push("updated_at");
+            }
+
+            query.push_str(&format!(
+                " ON CONFLICT ({}) ",
+                conflict_columns.join(", ")
+            ));
+            if update_columns.is_empty() {
+                query.push_str("DO NOTHING");
+            } else {
+                let assignments: Vec<String> = update_columns
+                    .iter()
+                    .map(|column| format!("{} = EXCLUDED.{}", column, column))
+                    .collect();
+                query.push_str(&format!("DO UPDATE SET {}", assignments.join(", ")));
+            }
+            query.push_str(" RETURNING *");
+
+            let mut query = sqlx::query(&query);
+            for (_, value) in values.iter().enumerate() {
+                query = query.bind(value);
+            }
+
+            match query.fetch_one(&pool).await {
+                Ok(row) => Ok(<$resource as DatabaseResource>::from_row(&row)?),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+}
+
+/// Inserts many rows of the same resource type in a single round-trip.
+///
+/// # Overview
+///
+/// Builds one multi-row `INSERT INTO ... VALUES (...), (...), ...` statement
+/// with correctly offset positional placeholders for each row, applying the
+/// same automatic `id`/`created_at`/`updated_at`/`expires_at` handling to
+/// every row independently, and returns all inserted rows from a single
+/// `RETURNING *`. This avoids N round-trips for bulk writes like ingesting a
+/// batch of alerts.
+///
+/// # Arguments
+/// * `$resource` - The type that implements the `DatabaseResource` trait
+/// * `$params_list` - A `Vec<Vec<(String, DatabaseValue)>>`, one inner Vec of
+///   `(field_name, DatabaseValue)` pairs per row to insert. Every row must
+///   provide the same set of fields.
+///
+/// # Example
+/// ```rust
+/// let rows = vec![
+///     vec![("title".to_string(), DatabaseValue::String("Disk full".to_string()))],
+///     vec![("title".to_string(), DatabaseValue::String("CPU high".to_string()))],
+/// ];
+/// let alerts = insert_many_resource!(Alert, rows).await?;
+/// ```
+#[macro_export]
+macro_rules! insert_many_resource {
+    ($resource:ty, $params_list:expr) => {{
+        use crate::database::{
+            connection::get_connection, traits::DatabaseResource, values::DatabaseValue,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+        use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime};
+        use uuid::Uuid;
+
+        let input_rows: Vec<Vec<(String, DatabaseValue)>> = $params_list;
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            // Build each row's full parameter list (including automatic
+            // fields) independently, but every row shares the same column
+            // order — derived from whichever row has the most columns, which
+            // is always every row if all rows provide the same fields.
+            let mut rows: Vec<Vec<(String, DatabaseValue)>> = Vec::new();
+            for input_row in input_rows.into_iter() {
+                let id = Uuid::new_v4().to_string();
+                let created_at = OffsetDateTime::now_utc().format(&Iso8601::DEFAULT).unwrap();
+                let updated_at = created_at.clone();
+                let expires_at = (OffsetDateTime::now_utc() + Duration::days(30))
+                    .format(&Iso8601::DEFAULT)
+                    .unwrap();
+
+                let mut row = input_row;
+
+                if <$resource as DatabaseResource>::has_id() {
+                    row.push(("id".to_string(), DatabaseValue::String(id)));
+                }
+                if <$resource as DatabaseResource>::is_creatable() {
+                    row.push((
+                        "created_at".to_string(),
+                        DatabaseValue::DateTime(created_at),
+                    ));
+                }
+                if <$resource as DatabaseResource>::is_updatable() {
+                    row.push((
+                        "updated_at".to_string(),
+                        DatabaseValue::DateTime(updated_at),
+                    ));
+                }
+                if <$resource as DatabaseResource>::is_expirable() {
+                    row.push((
+                        "expires_at".to_string(),
+                        DatabaseValue::DateTime(expires_at),
+                    ));
+                }
+
+                rows.push(row);
+            }
+
+            if rows.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            // Every row is expected to share the same column order.
+            let fields: Vec<String> = rows[0].iter().map(|(field, _)| field.clone()).collect();
+
+            let mut query = format!("INSERT INTO {} (", resource_name);
+            query.push_str(&fields.join(", "));
+            query.push_str(") VALUES ");
+
+            let mut all_values: Vec<DatabaseValue> = Vec::new();
+            let mut placeholder = 1usize;
+            for (row_idx, row) in rows.iter().enumerate() {
+                query.push('(');
+                for (col_idx, (_, value)) in row.iter().enumerate() {
+                    match value {
+                        DatabaseValue::None => query.push_str("NULL"),
+                        DatabaseValue::Str(_) | DatabaseValue::String(_) => {
+                            query.push_str(&format!("CAST(${} AS VARCHAR)", placeholder))
+                        }
+                        DatabaseValue::Text(_) => {
+                            query.push_str(&format!("CAST(${} AS TEXT)", placeholder))
+                        }
+                        DatabaseValue::DateTime(_) => {
+                            query.push_str(&format!("CAST(${} AS TIMESTAMP)", placeholder))
+                        }
+                        DatabaseValue::Int(_) => {
+                            query.push_str(&format!("CAST(${} AS INTEGER)", placeholder))
+                        }
+                        DatabaseValue::Int64(_) => {
+                            query.push_str(&format!("CAST(${} AS BIGINT)", placeholder))
+                        }
+                        DatabaseValue::Float(_) => {
+                            query.push_str(&format!("CAST(${} AS FLOAT)", placeholder))
+                        }
+                        DatabaseValue::Boolean(_) => {
+                            query.push_str(&format!("CAST(${} AS BOOLEAN)", placeholder))
+                        }
+                    }
+                    all_values.push(value.clone());
+                    placeholder += 1;
+                    if col_idx < row.len() - 1 {
+                        query.push_str(", ");
+                    }
+                }
+                query.push(')');
+                if row_idx < rows.len() - 1 {
+                    query.push_str(", ");
+                }
+            }
+            query.push_str(" RETURNING *");
+
+            let mut query = sqlx::query(&query);
+            for value in all_values.iter() {
+                query = query.bind(value);
+            }
+
+            match query.fetch_all(&pool).await {
+                Ok(fetched_rows) => fetched_rows
+                    .iter()
+                    .map(|row| <$resource as DatabaseResource>::from_row(row).map_err(DatabaseError::from))
+                    .collect::<Result<Vec<$resource>, _>>(),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+}
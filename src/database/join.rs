@@ -0,0 +1,68 @@
+//! Join specifications for `join_all_resources_where_fields_on!`.
+//!
+//! A `JoinSpec` names exactly which table to join, what kind of join to use,
+//! and the explicit `(primary_column, joined_column)` pair for the `ON`
+//! clause, instead of guessing join keys from a `{resource}_id` convention.
+
+/// The SQL join type to use for a `JoinSpec`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinKind {
+    Inner,
+    Left,
+}
+
+impl JoinKind {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            JoinKind::Inner => "INNER",
+            JoinKind::Left => "LEFT",
+        }
+    }
+}
+
+/// One join to apply against the primary resource's table.
+///
+/// `primary_column` and `joined_column` are the explicit `ON` keys, rendered
+/// as `primary_table.primary_column = table.joined_column` — there is no
+/// `{resource}_id` guessing.
+#[derive(Debug, Clone)]
+pub struct JoinSpec {
+    pub table: String,
+    pub kind: JoinKind,
+    pub primary_column: String,
+    pub joined_column: String,
+}
+
+impl JoinSpec {
+    pub fn new(
+        table: impl Into<String>,
+        kind: JoinKind,
+        primary_column: impl Into<String>,
+        joined_column: impl Into<String>,
+    ) -> Self {
+        Self {
+            table: table.into(),
+            kind,
+            primary_column: primary_column.into(),
+            joined_column: joined_column.into(),
+        }
+    }
+
+    /// Shorthand for `JoinSpec::new(table, JoinKind::Inner, primary_column, joined_column)`.
+    pub fn inner(
+        table: impl Into<String>,
+        primary_column: impl Into<String>,
+        joined_column: impl Into<String>,
+    ) -> Self {
+        Self::new(table, JoinKind::Inner, primary_column, joined_column)
+    }
+
+    /// Shorthand for `JoinSpec::new(table, JoinKind::Left, primary_column, joined_column)`.
+    pub fn left(
+        table: impl Into<String>,
+        primary_column: impl Into<String>,
+        joined_column: impl Into<String>,
+    ) -> Self {
+        Self::new(table, JoinKind::Left, primary_column, joined_column)
+    }
+}
@@ -1,127 +1,104 @@
-/// Performs a SQL JOIN query between two database tables and filters results based on provided parameters.
+/// Performs a SQL JOIN query across one or more tables and filters results based on a `Condition` tree.
 ///
 /// # Macro Arguments
-/// * `$resource` - The primary resource type that implements DatabaseResource trait
-/// * `$join_resource` - The resource type to join with
-/// * `$params` - A vector of tuples containing (field_name, value) pairs for WHERE clause filtering
+/// * `$resource` - The primary resource type that implements DatabaseResource trait; its table
+///   is the left-hand side of every join
+/// * `$joins` - A `Vec<JoinSpec>` naming each joined table, its join kind (`INNER`/`LEFT`), and
+///   the explicit `(primary_column, joined_column)` pair for its `ON` clause. Joins are chained
+///   in order, each one joining against the primary resource's table.
+/// * `$condition` - A `Condition` tree (see `database::predicate`) for the WHERE clause, sharing
+///   the same predicate/OR-tree handling as the `find_*` macros. A single placeholder counter is
+///   threaded through the whole WHERE clause by `Condition::render`.
 ///
 /// # Returns
 /// * `Result<Vec<$resource>, sqlx::Error>` - Returns a vector of primary resource instances or an error
 ///
 /// # Examples
 /// ```rust
-/// // Join Users with Stores and filter by store_id and active status
-/// let params = vec![("store_id", "123"), ("active", "true")];
-/// let users = join_all_resources_where_fields_on!(User, Store, params).await?;
+/// // Join Users with Stores on an explicit store_id = id key
+/// let users = join_all_resources_where_fields_on!(
+///     User,
+///     vec![JoinSpec::inner("stores", "store_id", "id")],
+///     Condition::pred("stores.active", DatabaseValue::Boolean(true.to_string()))
+/// ).await?;
 ///
-/// // Join UserRoles with Roles and filter by role_name
-/// let params = vec![("role_name", "admin")];
-/// let user_roles = join_all_resources_where_fields_on!(UserRole, Role, params).await?;
+/// // Chain a second LEFT JOIN
+/// let users = join_all_resources_where_fields_on!(
+///     User,
+///     vec![
+///         JoinSpec::inner("stores", "store_id", "id"),
+///         JoinSpec::left("store_settings", "store_id", "store_id"),
+///     ],
+///     Condition::pred("users.archived_at", Predicate::IsNull)
+/// ).await?;
 /// ```
 ///
 /// # Details
 /// This macro generates a SQL query that:
-/// 1. Converts resource names from CamelCase to snake_case (e.g., UserRole -> user_role)
-/// 2. Pluralizes table names (e.g., user_role -> user_roles)
-/// 3. Creates JOIN conditions using `{resource}_id` format (e.g., user_role_id)
-/// 4. Adds WHERE clause conditions based on provided parameters
-/// 5. Maps the results to the primary resource type using the DatabaseResource trait
-///
-/// # Generated SQL Example
-/// For `join_all_resources_where_fields_on!(User, Store, vec![("active", "true")])`:
-/// ```sql
-/// SELECT * FROM users
-/// JOIN stores ON store_id = user_id
-/// WHERE active = $1
-/// ```
+/// 1. Converts the primary resource name from CamelCase to snake_case and pluralizes it for the table name
+/// 2. Appends each `JoinSpec` as `{kind} JOIN {table} ON {primary_table}.{primary_column} = {table}.{joined_column}`
+/// 3. Qualifies the SELECT list as `{primary_table}.*` so joined columns never shadow the primary resource's own
+/// 4. Renders the `Condition` tree as the WHERE clause
+/// 5. Maps each row to the primary resource type, propagating conversion errors instead of panicking
 ///
 /// # Notes
 /// - The primary resource type must implement the DatabaseResource trait
-/// - Table names are automatically pluralized and converted to snake_case
-/// - Join conditions assume conventional ID naming (`{resource}_id`)
-/// - WHERE clause parameters are automatically parameterized to prevent SQL injection
+/// - Table names for joined tables are given explicitly via `JoinSpec`, not derived by convention
+/// - WHERE clause values are automatically parameterized to prevent SQL injection
 /// - The macro is asynchronous and must be awaited
-///
-/// # Panics
-/// - Will panic if the DatabaseResource::from_row conversion fails
-/// - May panic if the provided field names don't exist in the database
+/// - A row that fails to convert via `DatabaseResource::from_row` short-circuits the result as `Err`
 #[macro_export]
 macro_rules! join_all_resources_where_fields_on {
-    ($resource:ty, $join_resource:ty, $params:expr) => {{
-        use crate::database::{connection::get_connection, traits::DatabaseResource};
+    ($resource:ty, $joins:expr, $condition:expr) => {{
+        use crate::database::{connection::get_connection, join::JoinSpec, traits::DatabaseResource};
+        use crate::error::DatabaseError;
         use crate::utils::strings::camel_to_snake_case;
         use pluralizer::pluralize;
 
         async {
-            // Step 1: Process the primary resource name
-            // Convert CamelCase type name (e.g., UserRole) to snake_case (user_role)
+            // Step 1: Resolve the primary resource's table name
             let resource_name = camel_to_snake_case(stringify!($resource).to_string());
-            // Convert singular to plural for table name (e.g., user_role -> user_roles)
             let resource_table_name = pluralize(&resource_name, 2, false);
-            // Create the foreign key column name (e.g., user_role -> user_role_id)
-            let resource_join_name = format!("{}_id", resource_name);
-
-            // Step 2: Process the joined resource name using the same pattern
-            let join_resource_name = camel_to_snake_case(stringify!($join_resource).to_string());
-            let join_resource_table_name = pluralize(&join_resource_name, 2, false);
-            let join_resource_join_name = format!("{}_id", join_resource_name);
 
-            // Step 3: Get database connection from the connection pool
+            // Step 2: Get database connection from the connection pool
             let pool = get_connection().await;
 
-            // Step 4: Process the WHERE clause parameters
-            // Split the input params tuple vec into separate field names and values
-            // Example: vec![("store_id", "123"), ("active", "true")]
-            // Becomes: fields=["store_id", "active"], values=["123", "true"]
-            let fields = $params
-                .iter()
-                .map(|field| field.0.to_string())
-                .collect::<Vec<String>>();
-            let values = $params
-                .iter()
-                .map(|field| field.1.to_string())
-                .collect::<Vec<String>>();
-
-            // Step 5: Construct the base JOIN query
-            // Creates: "SELECT * FROM {table1} JOIN {table2} ON {fk} = {pk}"
+            // Step 3: Build the base SELECT + JOIN clauses, qualifying the SELECT list to
+            // the primary table so joined columns of the same name don't collide with it
+            let joins: Vec<JoinSpec> = $joins;
             let mut query = format!(
-                "SELECT * FROM {} JOIN {} ON {} = {}",
-                resource_table_name,      // First table (e.g., user_roles)
-                join_resource_table_name, // Second table (e.g., roles)
-                join_resource_join_name,  // Foreign key (e.g., role_id)
-                resource_join_name        // Primary key (e.g., user_role_id)
+                "SELECT {}.* FROM {}",
+                resource_table_name, resource_table_name
             );
-
-            // Step 6: Add WHERE clause conditions
-            // Adds parameterized conditions: "WHERE field1 = $1 AND field2 = $2"
-            query.push_str(" WHERE ");
-            for (i, field) in fields.iter().enumerate() {
-                // Add each condition with a numbered parameter placeholder
-                query.push_str(&format!("{} = ${}", field, i + 1));
-                // Add AND between conditions, but not after the last one
-                if i < fields.len() - 1 {
-                    query.push_str(" AND ");
-                }
+            for join in &joins {
+                query.push_str(&format!(
+                    " {} JOIN {} ON {}.{} = {}.{}",
+                    join.kind.as_sql(),
+                    join.table,
+                    resource_table_name,
+                    join.primary_column,
+                    join.table,
+                    join.joined_column
+                ));
             }
 
-            // Step 7: Create and prepare the SQL query
+            // Step 4: Render the WHERE clause, threading one placeholder counter across it
+            let (where_clause, bind_values, _) = $condition.render(1);
+            query.push_str(&format!(" WHERE {}", where_clause));
+
+            // Step 5: Create and prepare the SQL query, binding all values in order
             let mut query = sqlx::query(&query);
-            // Bind all parameter values in order
-            for (_, value) in values.iter().enumerate() {
+            for value in bind_values.iter() {
                 query = query.bind(value);
             }
 
-            // Step 8: Execute query and map results
+            // Step 6: Execute query and map results, propagating row-conversion errors
             match query.fetch_all(&pool).await {
-                Ok(rows) => {
-                    // Convert each database row into the requested resource type
-                    // using the DatabaseResource trait implementation
-                    Ok(rows
-                        .iter()
-                        .map(|row| <$resource as DatabaseResource>::from_row(row).unwrap())
-                        .collect::<Vec<$resource>>())
-                }
-                Err(e) => Err(e),
+                Ok(rows) => rows
+                    .iter()
+                    .map(|row| <$resource as DatabaseResource>::from_row(row).map_err(DatabaseError::from))
+                    .collect::<Result<Vec<$resource>, _>>(),
+                Err(e) => Err(DatabaseError::from(e)),
             }
         }
     }};
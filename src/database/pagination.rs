@@ -0,0 +1,34 @@
+//! Ordering and pagination helpers shared by the `find_all_*` macros.
+//!
+//! These mirror the building blocks of a SQL select-manager: a sort
+//! direction per column, and a rendered `ORDER BY` clause built from an
+//! ordered list of `(column, direction)` pairs. `LIMIT`/`OFFSET` are bound as
+//! ordinary parameters by the macros themselves, since they run after the
+//! WHERE clause's binds and need to continue the same placeholder count.
+
+/// Sort direction for a single `ORDER BY` column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+impl Direction {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            Direction::Asc => "ASC",
+            Direction::Desc => "DESC",
+        }
+    }
+}
+
+/// Renders an ordered list of `(column, direction)` pairs into the body of
+/// an `ORDER BY` clause (without the `ORDER BY` keyword itself), so callers
+/// can skip appending it entirely when the list is empty.
+pub fn render_order_by(order: &[(&str, Direction)]) -> String {
+    order
+        .iter()
+        .map(|(column, direction)| format!("{} {}", column, direction.as_sql()))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
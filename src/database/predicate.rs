@@ -0,0 +1,205 @@
+//! Predicate operators for WHERE clause construction.
+//!
+//! This module provides a small operator layer on top of `DatabaseValue` so the
+//! `find_*` macros can express more than bare equality comparisons. A plain
+//! `DatabaseValue` still works anywhere a `Predicate` is expected (it coerces to
+//! `Predicate::Eq`), so existing call sites that pass `vec![("field", value)]`
+//! do not need to change.
+//!
+//! Rendering a predicate is a two-part job: produce the SQL fragment for a
+//! single field (`field = $3`, `field IN ($4, $5, $6)`, `field IS NULL`, ...)
+//! and report how many placeholders it consumed, so callers that render many
+//! predicates in sequence can thread a single running placeholder index and
+//! bind list across the whole WHERE clause.
+
+use crate::database::values::DatabaseValue;
+
+/// A single comparison to apply to a field in a WHERE clause.
+#[derive(Debug, Clone)]
+pub enum Predicate {
+    /// `field = value`
+    Eq(DatabaseValue),
+    /// `field != value`
+    Ne(DatabaseValue),
+    /// `field IN (values...)`
+    In(Vec<DatabaseValue>),
+    /// `field LIKE pattern`
+    Like(String),
+    /// `field > value`
+    Gt(DatabaseValue),
+    /// `field >= value`
+    Gte(DatabaseValue),
+    /// `field < value`
+    Lt(DatabaseValue),
+    /// `field <= value`
+    Lte(DatabaseValue),
+    /// `field IS NULL` (binds nothing)
+    IsNull,
+    /// `field IS NOT NULL` (binds nothing)
+    IsNotNull,
+}
+
+/// A bare value used where a `Predicate` is expected coerces to equality,
+/// so `vec![("field", DatabaseValue::String(...))]` keeps working unchanged.
+impl From<DatabaseValue> for Predicate {
+    fn from(value: DatabaseValue) -> Self {
+        Predicate::Eq(value)
+    }
+}
+
+impl Predicate {
+    /// Renders this predicate as a SQL fragment for `field`, starting bind
+    /// placeholders at `next_index` (1-based, matching sqlx's `$n` syntax).
+    ///
+    /// Returns `(fragment, binds, next_index)` where `binds` is the ordered
+    /// list of values to bind for this predicate and `next_index` is the
+    /// placeholder index the *next* predicate should start from. `IsNull`
+    /// and `IsNotNull` bind nothing and leave `next_index` unchanged.
+    pub fn render(&self, field: &str, next_index: usize) -> (String, Vec<DatabaseValue>, usize) {
+        match self {
+            Predicate::Eq(value) => (
+                format!("{} = ${}", field, next_index),
+                vec![value.clone()],
+                next_index + 1,
+            ),
+            Predicate::Ne(value) => (
+                format!("{} != ${}", field, next_index),
+                vec![value.clone()],
+                next_index + 1,
+            ),
+            Predicate::Gt(value) => (
+                format!("{} > ${}", field, next_index),
+                vec![value.clone()],
+                next_index + 1,
+            ),
+            Predicate::Gte(value) => (
+                format!("{} >= ${}", field, next_index),
+                vec![value.clone()],
+                next_index + 1,
+            ),
+            Predicate::Lt(value) => (
+                format!("{} < ${}", field, next_index),
+                vec![value.clone()],
+                next_index + 1,
+            ),
+            Predicate::Lte(value) => (
+                format!("{} <= ${}", field, next_index),
+                vec![value.clone()],
+                next_index + 1,
+            ),
+            Predicate::Like(pattern) => (
+                format!("{} LIKE ${}", field, next_index),
+                vec![DatabaseValue::String(pattern.clone())],
+                next_index + 1,
+            ),
+            Predicate::IsNull => (format!("{} IS NULL", field), vec![], next_index),
+            Predicate::IsNotNull => (format!("{} IS NOT NULL", field), vec![], next_index),
+            Predicate::In(values) => {
+                let mut index = next_index;
+                let mut placeholders = Vec::with_capacity(values.len());
+                for _ in values {
+                    placeholders.push(format!("${}", index));
+                    index += 1;
+                }
+                (
+                    format!("{} IN ({})", field, placeholders.join(", ")),
+                    values.clone(),
+                    index,
+                )
+            }
+        }
+    }
+}
+
+/// Renders a full list of `(field, predicate)` pairs as a single WHERE clause,
+/// threading one placeholder index and one ordered bind list across all of
+/// them so `$n` numbering and bind order stay consistent.
+///
+/// `starting_index` lets callers that already emitted earlier placeholders
+/// (e.g. an `archived_at IS NULL` prefix) continue numbering from there.
+pub fn render_predicates<F, P>(
+    params: Vec<(F, P)>,
+    starting_index: usize,
+) -> (String, Vec<DatabaseValue>)
+where
+    F: AsRef<str>,
+    P: Into<Predicate>,
+{
+    let mut fragments = Vec::with_capacity(params.len());
+    let mut binds = Vec::new();
+    let mut next_index = starting_index;
+
+    for (field, predicate) in params.into_iter() {
+        let predicate: Predicate = predicate.into();
+        let (fragment, mut values, advanced) = predicate.render(field.as_ref(), next_index);
+        fragments.push(fragment);
+        binds.append(&mut values);
+        next_index = advanced;
+    }
+
+    (fragments.join(" AND "), binds)
+}
+
+/// A tree of predicates that can express `AND`/`OR` groups, not just a flat
+/// list joined by `AND`. Mirrors the `Predicate` leaf operators but allows
+/// arbitrary nesting: `Condition::And(vec![Condition::Or(vec![...]), ...])`.
+#[derive(Debug, Clone)]
+pub enum Condition {
+    /// A single `field op value` comparison.
+    Pred {
+        field: String,
+        op: Predicate,
+    },
+    /// All child conditions must hold; rendered as `(a AND b AND c)`.
+    And(Vec<Condition>),
+    /// Any child condition may hold; rendered as `(a OR b OR c)`.
+    Or(Vec<Condition>),
+}
+
+impl Condition {
+    /// Convenience constructor for a leaf predicate.
+    pub fn pred(field: impl Into<String>, op: impl Into<Predicate>) -> Self {
+        Condition::Pred {
+            field: field.into(),
+            op: op.into(),
+        }
+    }
+
+    /// Renders this condition tree depth-first, threading a single running
+    /// placeholder index and ordered bind list across the whole recursion so
+    /// `$n` numbers and bind order match traversal order.
+    ///
+    /// An empty `And` group renders as `TRUE` (vacuously satisfied) and an
+    /// empty `Or` group renders as `FALSE` (vacuously unsatisfiable), so
+    /// neither produces a dangling `AND`/`OR`.
+    pub fn render(&self, next_index: usize) -> (String, Vec<DatabaseValue>, usize) {
+        match self {
+            Condition::Pred { field, op } => op.render(field, next_index),
+            Condition::And(children) => Self::render_group(children, " AND ", "TRUE", next_index),
+            Condition::Or(children) => Self::render_group(children, " OR ", "FALSE", next_index),
+        }
+    }
+
+    fn render_group(
+        children: &[Condition],
+        joiner: &str,
+        empty: &str,
+        next_index: usize,
+    ) -> (String, Vec<DatabaseValue>, usize) {
+        if children.is_empty() {
+            return (empty.to_string(), vec![], next_index);
+        }
+
+        let mut fragments = Vec::with_capacity(children.len());
+        let mut binds = Vec::new();
+        let mut index = next_index;
+        for child in children {
+            let (fragment, mut values, advanced) = child.render(index);
+            fragments.push(fragment);
+            binds.append(&mut values);
+            index = advanced;
+        }
+
+        (format!("({})", fragments.join(joiner)), binds, index)
+    }
+}
@@ -6,35 +6,67 @@
 //!
 //! # Common Parameters
 //! - `$resource:ty`: The type of the resource (e.g., User, Store)
-//! - `$params:expr`: Vector of tuples containing field names and values: `vec![("field", value)]`
+//! - `$params:expr`: Vector of `(field, predicate)` tuples, where `predicate` is anything that
+//!   converts `Into<Predicate>` (a bare `DatabaseValue` coerces to `Predicate::Eq`, see
+//!   `database::predicate`). Placeholder numbering and bind order are resolved in a single pass
+//!   by `render_predicates`, so predicates like `In` that consume more than one bind stay in sync.
 //!
 //! # Resource Requirements
 //! Resources must implement the `DatabaseResource` trait which provides `from_row` functionality
 //! to convert database rows into the appropriate type.
 //!
+//! # Archived Rows
+//! The base `find_all_resources_where_fields!`, `count_resources_where_fields!`,
+//! `find_one_resource_where_fields!`, `find_all_resources_where_condition!`, and
+//! `find_one_resource_where_condition!` macros exclude soft-deleted rows by
+//! default for any resource whose `is_archivable()` is `true` — they AND in
+//! `archived_at IS NULL` alongside the caller's own predicates. Pass a
+//! trailing `include_archived` of `true` to opt out and see archived rows
+//! too. The explicit `_unarchived_`/`_archived_` macros are unaffected, since
+//! their archive-state scoping is already fixed by name.
+//!
 //! # Examples
 //!
 //! ```rust
 //! // Find all active users with a specific role
 //! let admins = find_all_unarchived_resources_where_fields!(
 //!     User,
-//!     vec![("role", "admin")]
+//!     vec![("role", DatabaseValue::String("admin".to_string()))]
 //! ).await?;
 //!
 //! // Find a single user by email
 //! let user = find_one_resource_where_fields!(
 //!     User,
-//!     vec![("email", "user@example.com")]
+//!     vec![("email", DatabaseValue::String("user@example.com".to_string()))]
+//! ).await?;
+//!
+//! // Find users whose role is one of a set, using the predicate operators directly
+//! let some_roles = find_all_resources_where_fields!(
+//!     User,
+//!     vec![("role", Predicate::In(vec![
+//!         DatabaseValue::String("admin".to_string()),
+//!         DatabaseValue::String("owner".to_string()),
+//!     ]))]
 //! ).await?;
 //! ```
 
 #[macro_export]
 macro_rules! find_all_resources_where_fields {
-    ($resource:ty, $params:expr) => {{
+    ($resource:ty, $params:expr) => {
+        $crate::find_all_resources_where_fields!($resource, $params, false)
+    };
+    // Same as the two-argument form, but `$include_archived` controls whether
+    // an archivable resource's soft-deleted rows are included. `false` (what
+    // the two-argument form passes) ANDs `archived_at IS NULL` around
+    // `$params` for any resource whose `is_archivable()` is `true`; `true`
+    // returns every row regardless of archive state. Non-archivable resources
+    // are unaffected either way.
+    ($resource:ty, $params:expr, $include_archived:expr) => {{
         // Import required traits and types for database operations
         use crate::database::{
-            connection::get_connection, traits::DatabaseResource, values::DatabaseValue,
+            connection::get_connection, predicate::render_predicates, traits::DatabaseResource,
         };
+        use crate::error::DatabaseError;
         use crate::utils::strings::camel_to_snake_case;
         use pluralizer::pluralize;
 
@@ -48,31 +80,21 @@ macro_rules! find_all_resources_where_fields {
             // Get database connection pool
             let pool = get_connection().await;
 
-            // Extract field names and values from parameters
-            // Example: vec![("email", value)] -> ["email"]
-            let fields = $params
-                .iter()
-                .map(|field| field.0.to_string())
-                .collect::<Vec<String>>();
-            // Example: vec![("email", value)] -> [value]
-            let values = $params
-                .iter()
-                .map(|field| field.1.clone())
-                .collect::<Vec<DatabaseValue>>();
+            // Render the WHERE clause and collect the ordered bind values in one pass
+            let (where_clause, bind_values) = render_predicates($params, 1);
+            let where_clause = if <$resource as DatabaseResource>::is_archivable() && !$include_archived {
+                format!("archived_at IS NULL AND {}", where_clause)
+            } else {
+                where_clause
+            };
 
             // Build the SQL query string with parameterized values
             // Example: "SELECT * FROM users WHERE email = $1 AND role = $2"
-            let mut query = format!("SELECT * FROM {} WHERE ", resource_name);
-            for (i, field) in fields.iter().enumerate() {
-                query.push_str(&format!("{} = ${}", field, i + 1));
-                if i < fields.len() - 1 {
-                    query.push_str(" AND ");
-                }
-            }
+            let query = format!("SELECT * FROM {} WHERE {}", resource_name, where_clause);
 
             // Create the SQL query and bind parameter values
             let mut query = sqlx::query(&query);
-            for value in values.iter() {
+            for value in bind_values.iter() {
                 query = query.bind(value);
             }
 
@@ -80,10 +102,141 @@ macro_rules! find_all_resources_where_fields {
             match query.fetch_all(&pool).await {
                 Ok(rows) => rows
                     .into_iter()
-                    .map(|row| <$resource as DatabaseResource>::from_row(&row))
+                    .map(|row| <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from))
+                    .collect::<Result<Vec<$resource>, _>>(),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+    // Paginated variant: adds column projection, ORDER BY, and LIMIT/OFFSET.
+    //
+    // * `$columns:expr` - `Vec<&str>` of columns to select; an empty vec selects `*`
+    // * `$order:expr` - `Vec<(&str, Direction)>` appended as `ORDER BY`; empty means unordered
+    // * `$limit:expr` / `$offset:expr` - `Option<i64>`, appended as bound `LIMIT`/`OFFSET` params
+    ($resource:ty, $params:expr, $columns:expr, $order:expr, $limit:expr, $offset:expr) => {
+        $crate::find_all_resources_where_fields!(
+            $resource, $params, $columns, $order, $limit, $offset, false
+        )
+    };
+    // Same as the six-argument paginated form, with the same trailing
+    // `$include_archived` as the two/three-argument forms above.
+    ($resource:ty, $params:expr, $columns:expr, $order:expr, $limit:expr, $offset:expr, $include_archived:expr) => {{
+        use crate::database::{
+            connection::get_connection,
+            pagination::render_order_by,
+            predicate::render_predicates,
+            traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let (where_clause, bind_values) = render_predicates($params, 1);
+            let where_clause = if <$resource as DatabaseResource>::is_archivable() && !$include_archived {
+                format!("archived_at IS NULL AND {}", where_clause)
+            } else {
+                where_clause
+            };
+            let columns: Vec<&str> = $columns;
+            let select_clause = if columns.is_empty() {
+                "*".to_string()
+            } else {
+                columns.join(", ")
+            };
+            let mut query = format!(
+                "SELECT {} FROM {} WHERE {}",
+                select_clause, resource_name, where_clause
+            );
+
+            let order_clause = render_order_by(&$order);
+            if !order_clause.is_empty() {
+                query.push_str(&format!(" ORDER BY {}", order_clause));
+            }
+
+            // LIMIT/OFFSET are bound parameters, continuing numbering after the WHERE binds
+            let mut next_index = bind_values.len() + 1;
+            let limit: Option<i64> = $limit;
+            let offset: Option<i64> = $offset;
+            if limit.is_some() {
+                query.push_str(&format!(" LIMIT ${}", next_index));
+                next_index += 1;
+            }
+            if offset.is_some() {
+                query.push_str(&format!(" OFFSET ${}", next_index));
+            }
+
+            let mut query = sqlx::query(&query);
+            for value in bind_values.iter() {
+                query = query.bind(value);
+            }
+            if let Some(limit) = limit {
+                query = query.bind(limit);
+            }
+            if let Some(offset) = offset {
+                query = query.bind(offset);
+            }
+
+            match query.fetch_all(&pool).await {
+                Ok(rows) => rows
+                    .into_iter()
+                    .map(|row| <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from))
                     .collect::<Result<Vec<$resource>, _>>(),
-                Err(e) => Err(e),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+}
+
+/// Runs the same WHERE clause as `find_all_resources_where_fields!` but with
+/// `SELECT COUNT(*)`, so a caller can report a total alongside a page of results.
+#[macro_export]
+macro_rules! count_resources_where_fields {
+    ($resource:ty, $params:expr) => {
+        $crate::count_resources_where_fields!($resource, $params, false)
+    };
+    // Same as the two-argument form, but `$include_archived` controls whether
+    // an archivable resource's soft-deleted rows are counted — see
+    // `find_all_resources_where_fields!` for the full explanation.
+    ($resource:ty, $params:expr, $include_archived:expr) => {{
+        use crate::database::{
+            connection::get_connection, predicate::render_predicates, traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let (where_clause, bind_values) = render_predicates($params, 1);
+            let where_clause = if <$resource as DatabaseResource>::is_archivable() && !$include_archived {
+                format!("archived_at IS NULL AND {}", where_clause)
+            } else {
+                where_clause
+            };
+            let query = format!(
+                "SELECT COUNT(*) FROM {} WHERE {}",
+                resource_name, where_clause
+            );
+
+            let mut query = sqlx::query_scalar::<_, i64>(&query);
+            for value in bind_values.iter() {
+                query = query.bind(value);
             }
+            query.fetch_one(&pool).await.map_err(DatabaseError::from)
         }
     }};
 }
@@ -93,7 +246,7 @@ macro_rules! find_all_resources_where_fields {
 ///
 /// # Arguments
 /// * `$resource:ty` - The type of resource to query
-/// * `$params:expr` - Vector of (field_name, value) tuples for WHERE conditions
+/// * `$params:expr` - Vector of `(field, predicate)` tuples for WHERE conditions
 ///
 /// # Returns
 /// * `Result<Vec<Resource>, Error>` - Collection of matching non-archived resources
@@ -102,14 +255,17 @@ macro_rules! find_all_resources_where_fields {
 /// ```rust
 /// let active_stores = find_all_unarchived_resources_where_fields!(
 ///     Store,
-///     vec![("owner_id", user_id)]
+///     vec![("owner_id", DatabaseValue::String(user_id))]
 /// ).await?;
 /// ```
 #[macro_export]
 macro_rules! find_all_unarchived_resources_where_fields {
     ($resource:ty, $params:expr) => {{
         // Import required traits and types
-        use crate::database::{connection::get_connection, traits::DatabaseResource};
+        use crate::database::{
+            connection::get_connection, predicate::render_predicates, traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
         use crate::utils::strings::camel_to_snake_case;
         use pluralizer::pluralize;
 
@@ -122,37 +278,93 @@ macro_rules! find_all_unarchived_resources_where_fields {
             );
             let pool = get_connection().await;
 
-            // Extract query parameters
-            let fields = $params
-                .iter()
-                .map(|field| field.0.to_string())
-                .collect::<Vec<String>>();
-            // Note: Using references to values here instead of cloning
-            let values = $params.iter().map(|field| &field.1).collect::<Vec<_>>();
+            let (where_clause, bind_values) = render_predicates($params, 1);
 
             // Build query with archived_at IS NULL condition
+            let query = format!(
+                "SELECT * FROM {} WHERE archived_at IS NULL AND {}",
+                resource_name, where_clause
+            );
+
+            // Create and execute parameterized query
+            let mut query = sqlx::query(&query);
+            for value in bind_values.iter() {
+                query = query.bind(value);
+            }
+            match query.fetch_all(&pool).await {
+                Ok(rows) => rows
+                    .into_iter()
+                    .map(|row| <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from))
+                    .collect::<Result<Vec<$resource>, _>>(),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+    // Paginated variant: see find_all_resources_where_fields! for argument details.
+    ($resource:ty, $params:expr, $columns:expr, $order:expr, $limit:expr, $offset:expr) => {{
+        use crate::database::{
+            connection::get_connection,
+            pagination::render_order_by,
+            predicate::render_predicates,
+            traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let (where_clause, bind_values) = render_predicates($params, 1);
+            let columns: Vec<&str> = $columns;
+            let select_clause = if columns.is_empty() {
+                "*".to_string()
+            } else {
+                columns.join(", ")
+            };
             let mut query = format!(
-                "SELECT * FROM {} WHERE archived_at IS NULL AND ",
-                resource_name
+                "SELECT {} FROM {} WHERE archived_at IS NULL AND {}",
+                select_clause, resource_name, where_clause
             );
-            for (i, field) in fields.iter().enumerate() {
-                query.push_str(&format!("{} = ${}", field, i + 1));
-                if i < fields.len() - 1 {
-                    query.push_str(" AND ");
-                }
+
+            let order_clause = render_order_by(&$order);
+            if !order_clause.is_empty() {
+                query.push_str(&format!(" ORDER BY {}", order_clause));
+            }
+
+            let mut next_index = bind_values.len() + 1;
+            let limit: Option<i64> = $limit;
+            let offset: Option<i64> = $offset;
+            if limit.is_some() {
+                query.push_str(&format!(" LIMIT ${}", next_index));
+                next_index += 1;
+            }
+            if offset.is_some() {
+                query.push_str(&format!(" OFFSET ${}", next_index));
             }
 
-            // Create and execute parameterized query
             let mut query = sqlx::query(&query);
-            for (_, value) in values.iter().enumerate() {
+            for value in bind_values.iter() {
                 query = query.bind(value);
             }
+            if let Some(limit) = limit {
+                query = query.bind(limit);
+            }
+            if let Some(offset) = offset {
+                query = query.bind(offset);
+            }
+
             match query.fetch_all(&pool).await {
                 Ok(rows) => rows
                     .into_iter()
-                    .map(|row| <$resource as DatabaseResource>::from_row(&row))
+                    .map(|row| <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from))
                     .collect::<Result<Vec<$resource>, _>>(),
-                Err(e) => Err(e),
+                Err(e) => Err(DatabaseError::from(e)),
             }
         }
     }};
@@ -163,7 +375,7 @@ macro_rules! find_all_unarchived_resources_where_fields {
 ///
 /// # Arguments
 /// * `$resource:ty` - The type of resource to query
-/// * `$params:expr` - Vector of (field_name, value) tuples for WHERE conditions
+/// * `$params:expr` - Vector of `(field, predicate)` tuples for WHERE conditions
 ///
 /// # Returns
 /// * `Result<Vec<Resource>, Error>` - Collection of matching archived resources
@@ -172,13 +384,55 @@ macro_rules! find_all_unarchived_resources_where_fields {
 /// ```rust
 /// let deleted_users = find_all_archived_resources_where_fields!(
 ///     User,
-///     vec![("department", "sales")]
+///     vec![("department", DatabaseValue::String("sales".to_string()))]
 /// ).await?;
 /// ```
 #[macro_export]
 macro_rules! find_all_archived_resources_where_fields {
     ($resource:ty, $params:expr) => {{
-        use crate::database::{connection::get_connection, traits::DatabaseResource};
+        use crate::database::{
+            connection::get_connection, predicate::render_predicates, traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let (where_clause, bind_values) = render_predicates($params, 1);
+            let query = format!(
+                "SELECT * FROM {} WHERE archived_at IS NOT NULL AND {}",
+                resource_name, where_clause
+            );
+
+            let mut query = sqlx::query(&query);
+            for value in bind_values.iter() {
+                query = query.bind(value);
+            }
+            match query.fetch_all(&pool).await {
+                Ok(rows) => rows
+                    .into_iter()
+                    .map(|row| <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from))
+                    .collect::<Result<Vec<$resource>, _>>(),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+    // Paginated variant: see find_all_resources_where_fields! for argument details.
+    ($resource:ty, $params:expr, $columns:expr, $order:expr, $limit:expr, $offset:expr) => {{
+        use crate::database::{
+            connection::get_connection,
+            pagination::render_order_by,
+            predicate::render_predicates,
+            traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
         use crate::utils::strings::camel_to_snake_case;
         use pluralizer::pluralize;
 
@@ -190,32 +444,51 @@ macro_rules! find_all_archived_resources_where_fields {
             );
             let pool = get_connection().await;
 
-            let fields = $params
-                .iter()
-                .map(|field| field.0.to_string())
-                .collect::<Vec<String>>();
-            let values = $params.iter().map(|field| &field.1).collect::<Vec<_>>();
+            let (where_clause, bind_values) = render_predicates($params, 1);
+            let columns: Vec<&str> = $columns;
+            let select_clause = if columns.is_empty() {
+                "*".to_string()
+            } else {
+                columns.join(", ")
+            };
             let mut query = format!(
-                "SELECT * FROM {} WHERE archived_at IS NOT NULL AND ",
-                resource_name
+                "SELECT {} FROM {} WHERE archived_at IS NOT NULL AND {}",
+                select_clause, resource_name, where_clause
             );
-            for (i, field) in fields.iter().enumerate() {
-                query.push_str(&format!("{} = ${}", field, i + 1));
-                if i < fields.len() - 1 {
-                    query.push_str(" AND ");
-                }
+
+            let order_clause = render_order_by(&$order);
+            if !order_clause.is_empty() {
+                query.push_str(&format!(" ORDER BY {}", order_clause));
+            }
+
+            let mut next_index = bind_values.len() + 1;
+            let limit: Option<i64> = $limit;
+            let offset: Option<i64> = $offset;
+            if limit.is_some() {
+                query.push_str(&format!(" LIMIT ${}", next_index));
+                next_index += 1;
+            }
+            if offset.is_some() {
+                query.push_str(&format!(" OFFSET ${}", next_index));
             }
 
             let mut query = sqlx::query(&query);
-            for (_, value) in values.iter().enumerate() {
+            for value in bind_values.iter() {
                 query = query.bind(value);
             }
+            if let Some(limit) = limit {
+                query = query.bind(limit);
+            }
+            if let Some(offset) = offset {
+                query = query.bind(offset);
+            }
+
             match query.fetch_all(&pool).await {
                 Ok(rows) => rows
                     .into_iter()
-                    .map(|row| <$resource as DatabaseResource>::from_row(&row))
+                    .map(|row| <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from))
                     .collect::<Result<Vec<$resource>, _>>(),
-                Err(e) => Err(e),
+                Err(e) => Err(DatabaseError::from(e)),
             }
         }
     }};
@@ -226,7 +499,13 @@ macro_rules! find_all_archived_resources_where_fields {
 ///
 /// # Arguments
 /// * `$resource:ty` - The type of resource to query
-/// * `$params:expr` - Vector of (field_name, value) tuples for WHERE conditions
+/// * `$params:expr` - Vector of `(field, predicate)` tuples for WHERE conditions
+/// * `$conn:expr` (optional) - A `&DbConn` to query within its request-scoped
+///   transaction (see `crate::database::transaction`) instead of a fresh pool
+///   connection
+/// * `$include_archived:literal` (optional, default `false`) - pass `true` to
+///   also match an archivable resource's soft-deleted rows; see "Archived
+///   Rows" in the module docs
 ///
 /// # Returns
 /// * `Result<Resource, Error>` - The matching resource or error if not found
@@ -235,13 +514,39 @@ macro_rules! find_all_archived_resources_where_fields {
 /// ```rust
 /// let user = find_one_resource_where_fields!(
 ///     User,
-///     vec![("id", user_id)]
+///     vec![("id", DatabaseValue::String(user_id))]
+/// ).await?;
+///
+/// // Within the request's shared transaction:
+/// let user = find_one_resource_where_fields!(
+///     User,
+///     vec![("id", DatabaseValue::String(user_id))],
+///     conn
+/// ).await?;
+///
+/// // Including soft-deleted rows:
+/// let user = find_one_resource_where_fields!(
+///     User,
+///     vec![("id", DatabaseValue::String(user_id))],
+///     true
 /// ).await?;
 /// ```
 #[macro_export]
 macro_rules! find_one_resource_where_fields {
-    ($resource:ty, $params:expr) => {{
-        use crate::database::{connection::get_connection, traits::DatabaseResource};
+    ($resource:ty, $params:expr) => {
+        $crate::find_one_resource_where_fields!($resource, $params, false)
+    };
+    // Same as the two-argument form, but `$include_archived` controls whether
+    // an archivable resource's soft-deleted rows are eligible — see
+    // `find_all_resources_where_fields!` for the full explanation. Matched as
+    // a `literal` (rather than `expr`, like `$conn` below) so a bare `true`/
+    // `false` at the call site picks this arm instead of being mistaken for a
+    // `DbConn` expression.
+    ($resource:ty, $params:expr, $include_archived:literal) => {{
+        use crate::database::{
+            connection::get_connection, predicate::render_predicates, traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
         use crate::utils::strings::camel_to_snake_case;
         use pluralizer::pluralize;
 
@@ -253,27 +558,67 @@ macro_rules! find_one_resource_where_fields {
             );
             let pool = get_connection().await;
 
-            let fields = $params
-                .iter()
-                .map(|field| field.0.to_string())
-                .collect::<Vec<String>>();
-            let values = $params.iter().map(|field| &field.1).collect::<Vec<_>>();
-            let mut query = format!("SELECT * FROM {} WHERE ", resource_name);
-            for (i, field) in fields.iter().enumerate() {
-                query.push_str(&format!("{} = ${}", field, i + 1));
-                if i < fields.len() - 1 {
-                    query.push_str(" AND ");
-                }
-            }
+            let (where_clause, bind_values) = render_predicates($params, 1);
+            let where_clause = if <$resource as DatabaseResource>::is_archivable() && !$include_archived {
+                format!("archived_at IS NULL AND {}", where_clause)
+            } else {
+                where_clause
+            };
+            let mut query = format!("SELECT * FROM {} WHERE {}", resource_name, where_clause);
             query.push_str(" LIMIT 1");
 
             let mut query = sqlx::query(&query);
-            for (_, value) in values.iter().enumerate() {
+            for value in bind_values.iter() {
                 query = query.bind(value);
             }
             match query.fetch_one(&pool).await {
-                Ok(row) => <$resource as DatabaseResource>::from_row(&row),
-                Err(e) => Err(e),
+                Ok(row) => <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+    // Same as the two-argument form, but executed against a request-scoped
+    // `DbConn` transaction (see `crate::database::transaction`) instead of a
+    // fresh pool connection.
+    ($resource:ty, $params:expr, $conn:expr) => {
+        $crate::find_one_resource_where_fields!($resource, $params, $conn, false)
+    };
+    // Same as the three-argument `$conn` form, with the same trailing
+    // `$include_archived` as above.
+    ($resource:ty, $params:expr, $conn:expr, $include_archived:literal) => {{
+        use crate::database::{predicate::render_predicates, traits::DatabaseResource};
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+
+            let (where_clause, bind_values) = render_predicates($params, 1);
+            let where_clause = if <$resource as DatabaseResource>::is_archivable() && !$include_archived {
+                format!("archived_at IS NULL AND {}", where_clause)
+            } else {
+                where_clause
+            };
+            let mut query = format!("SELECT * FROM {} WHERE {}", resource_name, where_clause);
+            query.push_str(" LIMIT 1");
+
+            let mut query = sqlx::query(&query);
+            for value in bind_values.iter() {
+                query = query.bind(value);
+            }
+
+            let mut guard = $conn.lock().await;
+            let tx = guard
+                .as_mut()
+                .expect("DbConn used after its transaction was finalized");
+            match query.fetch_one(&mut *tx).await {
+                Ok(row) => <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from),
+                Err(e) => Err(DatabaseError::from(e)),
             }
         }
     }};
@@ -284,7 +629,7 @@ macro_rules! find_one_resource_where_fields {
 ///
 /// # Arguments
 /// * `$resource:ty` - The type of resource to query
-/// * `$params:expr` - Vector of (field_name, value) tuples for WHERE conditions
+/// * `$params:expr` - Vector of `(field, predicate)` tuples for WHERE conditions
 ///
 /// # Returns
 /// * `Result<Resource, Error>` - The matching non-archived resource or error if not found
@@ -293,13 +638,16 @@ macro_rules! find_one_resource_where_fields {
 /// ```rust
 /// let active_user = find_one_unarchived_resource_where_fields!(
 ///     User,
-///     vec![("email", email)]
+///     vec![("email", DatabaseValue::String(email))]
 /// ).await?;
 /// ```
 #[macro_export]
 macro_rules! find_one_unarchived_resource_where_fields {
     ($resource:ty, $params:expr) => {{
-        use crate::database::{connection::get_connection, traits::DatabaseResource};
+        use crate::database::{
+            connection::get_connection, predicate::render_predicates, traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
         use crate::utils::strings::camel_to_snake_case;
         use pluralizer::pluralize;
 
@@ -311,30 +659,20 @@ macro_rules! find_one_unarchived_resource_where_fields {
             );
             let pool = get_connection().await;
 
-            let fields = $params
-                .iter()
-                .map(|field| field.0.to_string())
-                .collect::<Vec<String>>();
-            let values = $params.iter().map(|field| &field.1).collect::<Vec<_>>();
+            let (where_clause, bind_values) = render_predicates($params, 1);
             let mut query = format!(
-                "SELECT * FROM {} WHERE archived_at IS NULL AND ",
-                resource_name
+                "SELECT * FROM {} WHERE archived_at IS NULL AND {}",
+                resource_name, where_clause
             );
-            for (i, field) in fields.iter().enumerate() {
-                query.push_str(&format!("{} = ${}", field, i + 1));
-                if i < fields.len() - 1 {
-                    query.push_str(" AND ");
-                }
-            }
             query.push_str(" LIMIT 1");
 
             let mut query = sqlx::query(&query);
-            for (_, value) in values.iter().enumerate() {
+            for value in bind_values.iter() {
                 query = query.bind(value);
             }
             match query.fetch_one(&pool).await {
-                Ok(row) => <$resource as DatabaseResource>::from_row(&row),
-                Err(e) => Err(e),
+                Ok(row) => <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from),
+                Err(e) => Err(DatabaseError::from(e)),
             }
         }
     }};
@@ -345,7 +683,7 @@ macro_rules! find_one_unarchived_resource_where_fields {
 ///
 /// # Arguments
 /// * `$resource:ty` - The type of resource to query
-/// * `$params:expr` - Vector of (field_name, value) tuples for WHERE conditions
+/// * `$params:expr` - Vector of `(field, predicate)` tuples for WHERE conditions
 ///
 /// # Returns
 /// * `Result<Resource, Error>` - The matching archived resource or error if not found
@@ -354,13 +692,16 @@ macro_rules! find_one_unarchived_resource_where_fields {
 /// ```rust
 /// let deleted_store = find_one_archived_resource_where_fields!(
 ///     Store,
-///     vec![("id", store_id)]
+///     vec![("id", DatabaseValue::String(store_id))]
 /// ).await?;
 /// ```
 #[macro_export]
 macro_rules! find_one_archived_resource_where_fields {
     ($resource:ty, $params:expr) => {{
-        use crate::database::{connection::get_connection, traits::DatabaseResource};
+        use crate::database::{
+            connection::get_connection, predicate::render_predicates, traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
         use crate::utils::strings::camel_to_snake_case;
         use pluralizer::pluralize;
 
@@ -373,38 +714,311 @@ macro_rules! find_one_archived_resource_where_fields {
             );
             let pool = get_connection().await;
 
-            // Build query for archived records (archived_at IS NOT NULL)
+            // Render the WHERE clause for archived records (archived_at IS NOT NULL)
+            let (where_clause, bind_values) = render_predicates($params, 1);
             let mut query = format!(
-                "SELECT * FROM {} WHERE archived_at IS NOT NULL AND ",
-                resource_name
+                "SELECT * FROM {} WHERE archived_at IS NOT NULL AND {}",
+                resource_name, where_clause
             );
-
-            // Extract field names for WHERE clause
-            let fields = $params
-                .iter()
-                .map(|field| field.0.to_string())
-                .collect::<Vec<String>>();
-
-            // Build WHERE conditions with parameter placeholders
-            for (i, field) in fields.iter().enumerate() {
-                query.push_str(&format!("{} = ${}", field, i + 1));
-                if i < fields.len() - 1 {
-                    query.push_str(" AND ");
-                }
-            }
             // Limit to single result
             query.push_str(" LIMIT 1");
 
             // Create parameterized query and bind values
             let mut query = sqlx::query(&query);
-            for (_, value) in $params.iter().enumerate() {
-                query = query.bind(value.1);
+            for value in bind_values.iter() {
+                query = query.bind(value);
             }
 
             // Execute query and convert result to resource type
             match query.fetch_one(&pool).await {
                 Ok(row) => Ok(<$resource as DatabaseResource>::from_row(&row)?),
-                Err(e) => Err(e),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+}
+
+// --- Condition-tree variants ---------------------------------------------
+//
+// The `_where_fields!` macros above only ever AND a flat list of predicates
+// together. The `_where_condition!` macros below take a `Condition` tree
+// instead, so callers can express `(a = 1 OR b = 2) AND c = 3` by nesting
+// `Condition::And`/`Condition::Or`. Archived/unarchived variants AND an
+// `archived_at IS [NOT] NULL` leaf around the whole user-supplied tree
+// rather than hard-coding it as a string prefix, since the tree now owns
+// placeholder numbering end-to-end.
+
+/// Finds all resources matching an arbitrary `Condition` tree.
+///
+/// # Example
+/// ```rust
+/// let matches = find_all_resources_where_condition!(
+///     User,
+///     Condition::Or(vec![
+///         Condition::pred("role", DatabaseValue::String("admin".to_string())),
+///         Condition::pred("role", DatabaseValue::String("owner".to_string())),
+///     ])
+/// ).await?;
+/// ```
+#[macro_export]
+macro_rules! find_all_resources_where_condition {
+    ($resource:ty, $condition:expr) => {
+        $crate::find_all_resources_where_condition!($resource, $condition, false)
+    };
+    // Same as the two-argument form, but `$include_archived` controls whether
+    // an archivable resource's soft-deleted rows are included, ANDing
+    // `archived_at IS NULL` around `$condition` when `false` — see
+    // `find_all_resources_where_fields!` for the full explanation.
+    ($resource:ty, $condition:expr, $include_archived:expr) => {{
+        use crate::database::{
+            connection::get_connection, predicate::Condition, predicate::Predicate,
+            traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let scoped = if <$resource as DatabaseResource>::is_archivable() && !$include_archived {
+                Condition::And(vec![Condition::pred("archived_at", Predicate::IsNull), $condition])
+            } else {
+                $condition
+            };
+            let (where_clause, bind_values, _) = scoped.render(1);
+            let query = format!("SELECT * FROM {} WHERE {}", resource_name, where_clause);
+
+            let mut query = sqlx::query(&query);
+            for value in bind_values.iter() {
+                query = query.bind(value);
+            }
+            match query.fetch_all(&pool).await {
+                Ok(rows) => rows
+                    .into_iter()
+                    .map(|row| <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from))
+                    .collect::<Result<Vec<$resource>, _>>(),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+}
+
+/// Finds all non-archived resources matching an arbitrary `Condition` tree,
+/// ANDing `archived_at IS NULL` around the supplied condition.
+#[macro_export]
+macro_rules! find_all_unarchived_resources_where_condition {
+    ($resource:ty, $condition:expr) => {{
+        use crate::database::{
+            connection::get_connection, predicate::Condition, predicate::Predicate,
+            traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let scoped = Condition::And(vec![
+                Condition::pred("archived_at", Predicate::IsNull),
+                $condition,
+            ]);
+            let (where_clause, bind_values, _) = scoped.render(1);
+            let query = format!("SELECT * FROM {} WHERE {}", resource_name, where_clause);
+
+            let mut query = sqlx::query(&query);
+            for value in bind_values.iter() {
+                query = query.bind(value);
+            }
+            match query.fetch_all(&pool).await {
+                Ok(rows) => rows
+                    .into_iter()
+                    .map(|row| <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from))
+                    .collect::<Result<Vec<$resource>, _>>(),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+}
+
+/// Finds all archived resources matching an arbitrary `Condition` tree,
+/// ANDing `archived_at IS NOT NULL` around the supplied condition.
+#[macro_export]
+macro_rules! find_all_archived_resources_where_condition {
+    ($resource:ty, $condition:expr) => {{
+        use crate::database::{
+            connection::get_connection, predicate::Condition, predicate::Predicate,
+            traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let scoped = Condition::And(vec![
+                Condition::pred("archived_at", Predicate::IsNotNull),
+                $condition,
+            ]);
+            let (where_clause, bind_values, _) = scoped.render(1);
+            let query = format!("SELECT * FROM {} WHERE {}", resource_name, where_clause);
+
+            let mut query = sqlx::query(&query);
+            for value in bind_values.iter() {
+                query = query.bind(value);
+            }
+            match query.fetch_all(&pool).await {
+                Ok(rows) => rows
+                    .into_iter()
+                    .map(|row| <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from))
+                    .collect::<Result<Vec<$resource>, _>>(),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+}
+
+/// Finds a single resource matching an arbitrary `Condition` tree.
+#[macro_export]
+macro_rules! find_one_resource_where_condition {
+    ($resource:ty, $condition:expr) => {
+        $crate::find_one_resource_where_condition!($resource, $condition, false)
+    };
+    // Same as the two-argument form, but `$include_archived` controls whether
+    // an archivable resource's soft-deleted rows are eligible — see
+    // `find_all_resources_where_condition!` for the full explanation.
+    ($resource:ty, $condition:expr, $include_archived:expr) => {{
+        use crate::database::{
+            connection::get_connection, predicate::Condition, predicate::Predicate,
+            traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let scoped = if <$resource as DatabaseResource>::is_archivable() && !$include_archived {
+                Condition::And(vec![Condition::pred("archived_at", Predicate::IsNull), $condition])
+            } else {
+                $condition
+            };
+            let (where_clause, bind_values, _) = scoped.render(1);
+            let mut query = format!("SELECT * FROM {} WHERE {}", resource_name, where_clause);
+            query.push_str(" LIMIT 1");
+
+            let mut query = sqlx::query(&query);
+            for value in bind_values.iter() {
+                query = query.bind(value);
+            }
+            match query.fetch_one(&pool).await {
+                Ok(row) => <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+}
+
+/// Finds a single non-archived resource matching an arbitrary `Condition` tree,
+/// ANDing `archived_at IS NULL` around the supplied condition.
+#[macro_export]
+macro_rules! find_one_unarchived_resource_where_condition {
+    ($resource:ty, $condition:expr) => {{
+        use crate::database::{
+            connection::get_connection, predicate::Condition, predicate::Predicate,
+            traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let scoped = Condition::And(vec![
+                Condition::pred("archived_at", Predicate::IsNull),
+                $condition,
+            ]);
+            let (where_clause, bind_values, _) = scoped.render(1);
+            let mut query = format!("SELECT * FROM {} WHERE {}", resource_name, where_clause);
+            query.push_str(" LIMIT 1");
+
+            let mut query = sqlx::query(&query);
+            for value in bind_values.iter() {
+                query = query.bind(value);
+            }
+            match query.fetch_one(&pool).await {
+                Ok(row) => <$resource as DatabaseResource>::from_row(&row).map_err(DatabaseError::from),
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+}
+
+/// Finds a single archived resource matching an arbitrary `Condition` tree,
+/// ANDing `archived_at IS NOT NULL` around the supplied condition.
+#[macro_export]
+macro_rules! find_one_archived_resource_where_condition {
+    ($resource:ty, $condition:expr) => {{
+        use crate::database::{
+            connection::get_connection, predicate::Condition, predicate::Predicate,
+            traits::DatabaseResource,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+
+        async {
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let scoped = Condition::And(vec![
+                Condition::pred("archived_at", Predicate::IsNotNull),
+                $condition,
+            ]);
+            let (where_clause, bind_values, _) = scoped.render(1);
+            let mut query = format!("SELECT * FROM {} WHERE {}", resource_name, where_clause);
+            query.push_str(" LIMIT 1");
+
+            let mut query = sqlx::query(&query);
+            for value in bind_values.iter() {
+                query = query.bind(value);
+            }
+            match query.fetch_one(&pool).await {
+                Ok(row) => Ok(<$resource as DatabaseResource>::from_row(&row)?),
+                Err(e) => Err(DatabaseError::from(e)),
             }
         }
     }};
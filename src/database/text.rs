@@ -0,0 +1,74 @@
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef};
+use sqlx::{encode::IsNull, error::BoxDynError, Decode, Encode, Postgres, Type};
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// Stores any `Display`/`FromStr` type in a text column, mirroring sqlx's own
+/// `sqlx::types::Text` adapter.
+///
+/// `DatabaseValue` only covers a fixed set of variants, so a custom enum, a
+/// `uuid`-like id, or any other formatted struct has nowhere to go without
+/// hand-rolling its own `Encode`/`Decode` impls. Wrapping it in `Text` instead
+/// writes it out via `Display` and reads it back via `FromStr`, as long as
+/// `T::Err` is a real error type.
+///
+/// # Example
+/// ```rust
+/// #[derive(Debug, Display, FromStr)]
+/// enum Role { Admin, Owner }
+///
+/// sqlx::query("INSERT INTO users (role) VALUES ($1)")
+///     .bind(Text(Role::Admin))
+///     .execute(&pool)
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Text<T>(pub T);
+
+impl<T> Text<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Text<T> {
+    fn from(value: T) -> Self {
+        Text(value)
+    }
+}
+
+impl<T> Deref for Text<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> Type<Postgres> for Text<T> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("text")
+    }
+}
+
+impl<'q, T> Encode<'q, Postgres> for Text<T>
+where
+    T: Display,
+{
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        Encode::<Postgres>::encode_by_ref(&self.0.to_string(), buf)
+    }
+}
+
+impl<'r, T> Decode<'r, Postgres> for Text<T>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let s = <String as Decode<Postgres>>::decode(value)?;
+        Ok(Text(s.parse()?))
+    }
+}
@@ -0,0 +1,169 @@
+//! Request-scoped database transactions.
+//!
+//! By default every `find_one_resource_where_fields!`/`insert_resource!`/etc.
+//! invocation opens its own connection from the pool and commits
+//! independently, so a request that performs several inserts plus a lookup
+//! (e.g. verifying a token, then creating a record) can't roll them all back
+//! together if one of them fails partway through.
+//!
+//! `DbConn` is a request guard that lazily opens a single
+//! `sqlx::Transaction<Postgres>` the first time it's requested, caches it in
+//! Rocket's request-local storage, and hands every other guard or handler
+//! function in the same request a reference to that same transaction. Pass
+//! it as the trailing `$conn` argument to the macros that support it to run
+//! them against it instead of a fresh pool connection. [`DbConnFairing`]
+//! then commits the transaction on a successful response and rolls it back
+//! otherwise — it's a no-op for requests that never opened one.
+//!
+//! # Example
+//! ```rust
+//! #[post("/")]
+//! async fn create_user(conn: &DbConn, params: Json<NewUser>) -> ... {
+//!     let user = insert_resource!(User, params.into_inner(), conn).await?;
+//!     let _ = insert_resource!(Authentication, auth_params, conn).await?;
+//!     // Either both rows land, or (on any later error in this request)
+//!     // neither does — DbConnFairing rolls the transaction back.
+//! }
+//! ```
+//!
+//! [`with_transaction`] gives the same unit-of-work outside of a request —
+//! a background job or CLI command has no `&DbConn` guard to borrow, so it
+//! opens and finalizes its own `DbConn` directly instead of relying on
+//! `DbConnFairing`.
+
+use crate::database::connection::get_connection;
+use crate::error::DatabaseError;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::{Request, Response};
+use sqlx::{Postgres, Transaction};
+use tokio::sync::{Mutex, MutexGuard, OnceCell};
+
+/// One request's lazily-opened transaction. `None` once [`DbConnFairing`]
+/// has committed or rolled it back.
+pub struct DbConn(Mutex<Option<Transaction<'static, Postgres>>>);
+
+/// The request-local slot `DbConn`'s `FromRequest` impl and `DbConnFairing`
+/// both read. A plain `OnceCell` (rather than eagerly opening a transaction
+/// for every request) means requests that never ask for a `DbConn` never
+/// pay for one.
+type Slot = OnceCell<Result<DbConn, String>>;
+
+impl DbConn {
+    fn new(tx: Transaction<'static, Postgres>) -> Self {
+        Self(Mutex::new(Some(tx)))
+    }
+
+    /// Locks the live transaction for use as an `sqlx::Executor`, e.g.
+    /// `query.fetch_one(&mut *conn.lock().await)`.
+    ///
+    /// Panics if called after `DbConnFairing` has already finalized the
+    /// transaction, which can't happen during normal request handling since
+    /// the fairing only runs once the handler has returned.
+    pub async fn lock(&self) -> MutexGuard<'_, Option<Transaction<'static, Postgres>>> {
+        self.0.lock().await
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for &'r DbConn {
+    type Error = DatabaseError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let slot: &Slot = request.local_cache(Slot::new);
+        let result = slot
+            .get_or_init(|| async {
+                let pool = get_connection().await;
+                pool.begin()
+                    .await
+                    .map(DbConn::new)
+                    .map_err(|e| e.to_string())
+            })
+            .await;
+
+        match result {
+            Ok(conn) => Outcome::Success(conn),
+            Err(message) => Outcome::Error((
+                Status::InternalServerError,
+                DatabaseError::TransactionFailed(message.clone()),
+            )),
+        }
+    }
+}
+
+/// Commits or rolls back the request's `DbConn` transaction, if one was opened.
+///
+/// Requests that never use a `DbConn` guard are untouched. Otherwise: commits
+/// when the response status is a success (2xx), rolls back for anything else
+/// (including errors raised after a handler already wrote to the transaction).
+pub struct DbConnFairing;
+
+#[rocket::async_trait]
+impl Fairing for DbConnFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "DbConn transaction commit/rollback",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let slot: &Slot = request.local_cache(Slot::new);
+        let Some(Ok(conn)) = slot.get() else {
+            return;
+        };
+        let Some(tx) = conn.0.lock().await.take() else {
+            return;
+        };
+
+        if response.status().class().is_success() {
+            let _ = tx.commit().await;
+        } else {
+            let _ = tx.rollback().await;
+        }
+    }
+}
+
+/// Runs `body` as a single unit of work against a freshly opened transaction,
+/// independent of any Rocket request — for multi-step mutations outside
+/// request handling (a background job, a CLI command, startup seeding) where
+/// there's no `&DbConn` request guard to borrow.
+///
+/// Opens a transaction from the pool and hands `body` a `&DbConn` wrapping
+/// it, so it binds against the macros' trailing `$conn` argument exactly
+/// like a request-scoped one. Commits if `body` returns `Ok`, rolls back
+/// otherwise, and returns `body`'s result either way.
+///
+/// # Example
+/// ```rust
+/// with_transaction(|conn| async move {
+///     let authentication = insert_resource!(Authentication, auth_params, conn).await?;
+///     delete_resource_where_fields!(UserToken, revoke_params, conn).await?;
+///     Ok(authentication)
+/// }).await?;
+/// ```
+pub async fn with_transaction<F, Fut, T>(body: F) -> Result<T, DatabaseError>
+where
+    F: FnOnce(&DbConn) -> Fut,
+    Fut: std::future::Future<Output = Result<T, DatabaseError>>,
+{
+    let pool = get_connection().await;
+    let tx = pool
+        .begin()
+        .await
+        .map_err(|e| DatabaseError::TransactionFailed(e.to_string()))?;
+    let conn = DbConn::new(tx);
+
+    let result = body(&conn).await;
+
+    if let Some(tx) = conn.0.lock().await.take() {
+        if result.is_ok() {
+            let _ = tx.commit().await;
+        } else {
+            let _ = tx.rollback().await;
+        }
+    }
+
+    result
+}
@@ -45,8 +45,10 @@
 macro_rules! update_resource {
     ($resource:ty, $id:expr, $params:expr) => {{
         use crate::database::{
+            change_feed::{self, ChangeEvent, ChangeOp},
             connection::get_connection, traits::DatabaseResource, values::DatabaseValue,
         };
+        use crate::error::DatabaseError;
         use crate::find_one_resource_where_fields;
         use crate::utils::strings::camel_to_snake_case;
         use pluralizer::pluralize;
@@ -170,15 +172,320 @@ macro_rules! update_resource {
             // Execute the UPDATE query
             match query.execute(&pool).await {
                 Ok(_) => (),
-                Err(e) => return Err(e),
+                Err(e) => return Err(DatabaseError::from(e)),
             };
 
             // Fetch and return the updated resource
-            let params = vec![("id", &$id)];
-            match find_one_resource_where_fields!($resource, params).await {
-                Ok(resource) => Ok(resource),
+            let lookup_params = vec![("id", DatabaseValue::String($id.to_string()))];
+            match find_one_resource_where_fields!($resource, lookup_params).await {
+                Ok(resource) => {
+                    let values_owned: Vec<DatabaseValue> =
+                        params.iter().map(|(_, value)| value.clone()).collect();
+                    let user_id = change_feed::find_user_id(&fields, &values_owned);
+                    change_feed::publish(ChangeEvent {
+                        resource: stringify!($resource).to_string(),
+                        table: resource_name,
+                        op: ChangeOp::Updated,
+                        id: $id.to_string(),
+                        changed_fields: fields,
+                        user_id,
+                        timestamp: change_feed::now(),
+                    });
+                    Ok(resource)
+                }
                 Err(e) => Err(e),
             }
         }
     }};
+    // Same as the three-argument form, but executed against a request-scoped
+    // `DbConn` transaction (see `crate::database::transaction`) instead of a
+    // fresh pool connection.
+    ($resource:ty, $id:expr, $params:expr, $conn:expr) => {{
+        use crate::database::{
+            change_feed::{self, ChangeEvent, ChangeOp},
+            traits::DatabaseResource, values::DatabaseValue,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+        use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime};
+
+        async {
+            let updated_at = OffsetDateTime::now_utc().format(&Iso8601::DEFAULT).unwrap();
+            let expires_at = (OffsetDateTime::now_utc() + Duration::days(30))
+                .format(&Iso8601::DEFAULT)
+                .unwrap();
+
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+
+            let mut params: Vec<(&str, DatabaseValue)> = Vec::new();
+            let input_params: Vec<(&str, DatabaseValue)> = $params;
+            if !input_params.is_empty() {
+                for (field, value) in input_params {
+                    params.push((field, value.clone()));
+                }
+            }
+
+            if <$resource as DatabaseResource>::is_updatable() {
+                if let Some(idx) = params
+                    .iter()
+                    .position(|(field, _)| field.contains("updated_at"))
+                {
+                    params[idx] = ("updated_at", DatabaseValue::DateTime(updated_at));
+                } else {
+                    params.push(("updated_at", DatabaseValue::DateTime(updated_at)));
+                }
+            }
+
+            if <$resource as DatabaseResource>::is_expirable() {
+                if let Some(idx) = params
+                    .iter()
+                    .position(|(field, _)| field.contains("expires_at"))
+                {
+                    params[idx] = ("expires_at", DatabaseValue::DateTime(expires_at));
+                } else {
+                    params.push(("expires_at", DatabaseValue::DateTime(expires_at)));
+                }
+            }
+
+            let fields = params
+                .iter()
+                .map(|(field, _)| field.to_string())
+                .collect::<Vec<String>>();
+            let values: Vec<&DatabaseValue> = params.iter().map(|(_, value)| value).collect();
+
+            let mut query = format!("UPDATE {} SET ", resource_name);
+            for (i, field) in fields.iter().enumerate() {
+                let value = values[i];
+                match value {
+                    DatabaseValue::None => {
+                        query.push_str(&format!("{} = NULL", field));
+                    }
+                    DatabaseValue::Str(_) | DatabaseValue::String(_) | DatabaseValue::Text(_) => {
+                        query.push_str(&format!("{} = ${}", field, i + 1));
+                    }
+                    DatabaseValue::DateTime(_) => {
+                        query.push_str(&format!("{} = CAST(${} AS TIMESTAMP)", field, i + 1));
+                    }
+                    DatabaseValue::Int(_) => {
+                        query.push_str(&format!("{} = CAST(${} AS INTEGER)", field, i + 1));
+                    }
+                    DatabaseValue::Int64(_) => {
+                        query.push_str(&format!("{} = CAST(${} AS BIGINT)", field, i + 1));
+                    }
+                    DatabaseValue::Float(_) => {
+                        query.push_str(&format!("{} = CAST(${} AS FLOAT)", field, i + 1));
+                    }
+                    DatabaseValue::Boolean(_) => {
+                        query.push_str(&format!("{} = CAST(${} AS BOOLEAN)", field, i + 1));
+                    }
+                }
+                if i < fields.len() - 1 {
+                    query.push_str(", ");
+                }
+            }
+
+            query.push_str(&format!(" WHERE id = ${}", fields.len() + 1));
+            query.push_str(" RETURNING *");
+
+            let mut query = sqlx::query(&query);
+            for (_, value) in values.iter().enumerate() {
+                match value {
+                    DatabaseValue::None => query = query.bind(Option::<String>::None),
+                    _ => query = query.bind(value),
+                }
+            }
+            query = query.bind(&$id);
+
+            let mut guard = $conn.lock().await;
+            let tx = guard
+                .as_mut()
+                .expect("DbConn used after its transaction was finalized");
+            match query.fetch_one(&mut *tx).await {
+                Ok(row) => {
+                    let resource = <$resource as DatabaseResource>::from_row(&row)?;
+                    let values_owned: Vec<DatabaseValue> =
+                        params.iter().map(|(_, value)| value.clone()).collect();
+                    let user_id = change_feed::find_user_id(&fields, &values_owned);
+                    change_feed::publish(ChangeEvent {
+                        resource: stringify!($resource).to_string(),
+                        table: resource_name,
+                        op: ChangeOp::Updated,
+                        id: $id.to_string(),
+                        changed_fields: fields,
+                        user_id,
+                        timestamp: change_feed::now(),
+                    });
+                    Ok(resource)
+                }
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
+}
+
+/// Updates every resource matching an arbitrary `Condition` tree, rather than
+/// a single resource by id.
+///
+/// The `SET` clause logic (field casting, auto `updated_at`/`expires_at`
+/// handling) is identical to `update_resource!`; only what's matched in the
+/// `WHERE` clause changes, via `database::predicate` — so "bump every session
+/// whose `expires_at < now`" becomes possible, not just a lookup by primary
+/// key. Since a condition can match more than one row, this returns every
+/// updated resource instead of a single one.
+///
+/// # Example
+/// ```rust
+/// use crate::database::predicate::{Condition, Predicate};
+///
+/// let renewed = update_resource_where_condition!(
+///     Session,
+///     vec![("revoked", DatabaseValue::Boolean(true))],
+///     Condition::pred("expires_at", Predicate::Lt(DatabaseValue::DateTime(now)))
+/// ).await?;
+/// ```
+#[macro_export]
+macro_rules! update_resource_where_condition {
+    ($resource:ty, $params:expr, $condition:expr) => {{
+        use crate::database::{
+            change_feed::{self, ChangeEvent, ChangeOp},
+            connection::get_connection, traits::DatabaseResource, values::DatabaseValue,
+        };
+        use crate::error::DatabaseError;
+        use crate::utils::strings::camel_to_snake_case;
+        use pluralizer::pluralize;
+        use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime};
+
+        async {
+            let updated_at = OffsetDateTime::now_utc().format(&Iso8601::DEFAULT).unwrap();
+            let expires_at = (OffsetDateTime::now_utc() + Duration::days(30))
+                .format(&Iso8601::DEFAULT)
+                .unwrap();
+
+            let resource_name = pluralize(
+                camel_to_snake_case(stringify!($resource).to_string()).as_str(),
+                2,
+                false,
+            );
+            let pool = get_connection().await;
+
+            let mut params: Vec<(&str, DatabaseValue)> = Vec::new();
+            let input_params: Vec<(&str, DatabaseValue)> = $params;
+            if !input_params.is_empty() {
+                for (field, value) in input_params {
+                    params.push((field, value.clone()));
+                }
+            }
+
+            if <$resource as DatabaseResource>::is_updatable() {
+                if let Some(idx) = params
+                    .iter()
+                    .position(|(field, _)| field.contains("updated_at"))
+                {
+                    params[idx] = ("updated_at", DatabaseValue::DateTime(updated_at));
+                } else {
+                    params.push(("updated_at", DatabaseValue::DateTime(updated_at)));
+                }
+            }
+
+            if <$resource as DatabaseResource>::is_expirable() {
+                if let Some(idx) = params
+                    .iter()
+                    .position(|(field, _)| field.contains("expires_at"))
+                {
+                    params[idx] = ("expires_at", DatabaseValue::DateTime(expires_at));
+                } else {
+                    params.push(("expires_at", DatabaseValue::DateTime(expires_at)));
+                }
+            }
+
+            let fields = params
+                .iter()
+                .map(|(field, _)| field.to_string())
+                .collect::<Vec<String>>();
+            let values: Vec<&DatabaseValue> = params.iter().map(|(_, value)| value).collect();
+
+            let mut query = format!("UPDATE {} SET ", resource_name);
+            for (i, field) in fields.iter().enumerate() {
+                let value = values[i];
+                match value {
+                    DatabaseValue::None => {
+                        query.push_str(&format!("{} = NULL", field));
+                    }
+                    DatabaseValue::Str(_) | DatabaseValue::String(_) | DatabaseValue::Text(_) => {
+                        query.push_str(&format!("{} = ${}", field, i + 1));
+                    }
+                    DatabaseValue::DateTime(_) => {
+                        query.push_str(&format!("{} = CAST(${} AS TIMESTAMP)", field, i + 1));
+                    }
+                    DatabaseValue::Int(_) => {
+                        query.push_str(&format!("{} = CAST(${} AS INTEGER)", field, i + 1));
+                    }
+                    DatabaseValue::Int64(_) => {
+                        query.push_str(&format!("{} = CAST(${} AS BIGINT)", field, i + 1));
+                    }
+                    DatabaseValue::Float(_) => {
+                        query.push_str(&format!("{} = CAST(${} AS FLOAT)", field, i + 1));
+                    }
+                    DatabaseValue::Boolean(_) => {
+                        query.push_str(&format!("{} = CAST(${} AS BOOLEAN)", field, i + 1));
+                    }
+                }
+                if i < fields.len() - 1 {
+                    query.push_str(", ");
+                }
+            }
+
+            // The condition tree's placeholders continue numbering right after the SET clause's.
+            let (where_clause, condition_binds, _) = $condition.render(fields.len() + 1);
+            query.push_str(&format!(" WHERE {}", where_clause));
+            query.push_str(" RETURNING *");
+
+            let mut query = sqlx::query(&query);
+            for value in values.iter() {
+                match value {
+                    DatabaseValue::None => query = query.bind(Option::<String>::None),
+                    _ => query = query.bind(*value),
+                }
+            }
+            for value in condition_binds.iter() {
+                query = query.bind(value);
+            }
+
+            let values_owned: Vec<DatabaseValue> =
+                params.iter().map(|(_, value)| value.clone()).collect();
+            let user_id = change_feed::find_user_id(&fields, &values_owned);
+
+            match query.fetch_all(&pool).await {
+                Ok(rows) => {
+                    let resources = rows
+                        .into_iter()
+                        .map(|row| {
+                            <$resource as DatabaseResource>::from_row(&row)
+                                .map_err(DatabaseError::from)
+                        })
+                        .collect::<Result<Vec<$resource>, _>>()?;
+
+                    for resource in &resources {
+                        change_feed::publish(ChangeEvent {
+                            resource: stringify!($resource).to_string(),
+                            table: resource_name.clone(),
+                            op: ChangeOp::Updated,
+                            id: resource.id.clone().unwrap_or_default(),
+                            changed_fields: fields.clone(),
+                            user_id: user_id.clone(),
+                            timestamp: change_feed::now(),
+                        });
+                    }
+
+                    Ok(resources)
+                }
+                Err(e) => Err(DatabaseError::from(e)),
+            }
+        }
+    }};
 }
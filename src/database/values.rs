@@ -1,7 +1,8 @@
-use sqlx::postgres::PgArgumentBuffer;
-use sqlx::{encode::IsNull, error::BoxDynError, Encode, Postgres, Type};
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef};
+use sqlx::{encode::IsNull, error::BoxDynError, Decode, Encode, Postgres, Type, ValueRef};
 use std::fmt::{self, Display};
 use std::iter::FromIterator;
+use std::str::FromStr;
 use time::format_description::well_known::Iso8601;
 use time::OffsetDateTime;
 
@@ -40,6 +41,11 @@ pub enum DatabaseValue {
     /// Represents a datetime value stored as an ISO 8601 formatted String
     #[allow(dead_code)]
     DateTime(String),
+    /// Represents a SQL array, one element per item. Built by the
+    /// numeric/bool/datetime `FromIterator` impls below, and encoded as a
+    /// real Postgres array rather than a concatenated string.
+    #[allow(dead_code)]
+    Array(Vec<DatabaseValue>),
 }
 
 /// Implements string representation for DatabaseValue for debugging and logging purposes
@@ -51,6 +57,11 @@ impl Display for DatabaseValue {
 
 /// Implements encoding for PostgreSQL database operations.
 /// This allows DatabaseValue to be used directly in SQL queries with sqlx.
+///
+/// Every variant stores its value as a `String` (see the enum docs), but is
+/// parsed back into its real Rust type here and encoded via that type's own
+/// `Encode<Postgres>` impl, so the value reaches the wire in its native
+/// binary format rather than as text the server has to cast.
 impl<'q> Encode<'q, Postgres> for DatabaseValue {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
         match self {
@@ -58,32 +69,288 @@ impl<'q> Encode<'q, Postgres> for DatabaseValue {
             DatabaseValue::Str(s) => Encode::<Postgres>::encode_by_ref(s, buf),
             DatabaseValue::String(s) => Encode::<Postgres>::encode_by_ref(s, buf),
             DatabaseValue::Text(s) => Encode::<Postgres>::encode_by_ref(s, buf),
-            DatabaseValue::Int(i) => Encode::<Postgres>::encode_by_ref(i, buf),
-            DatabaseValue::Int64(i) => Encode::<Postgres>::encode_by_ref(i, buf),
-            DatabaseValue::Float(f) => Encode::<Postgres>::encode_by_ref(f, buf),
-            DatabaseValue::Boolean(b) => Encode::<Postgres>::encode_by_ref(b, buf),
-            DatabaseValue::DateTime(dt) => Encode::<Postgres>::encode_by_ref(dt, buf),
+            DatabaseValue::Int(i) => Encode::<Postgres>::encode_by_ref(&i32::from_str(i)?, buf),
+            DatabaseValue::Int64(i) => Encode::<Postgres>::encode_by_ref(&i64::from_str(i)?, buf),
+            DatabaseValue::Float(f) => Encode::<Postgres>::encode_by_ref(&f64::from_str(f)?, buf),
+            DatabaseValue::Boolean(b) => Encode::<Postgres>::encode_by_ref(&bool::from_str(b)?, buf),
+            DatabaseValue::DateTime(dt) => {
+                let parsed = OffsetDateTime::parse(dt, &Iso8601::DEFAULT)?;
+                Encode::<Postgres>::encode_by_ref(&parsed, buf)
+            }
+            DatabaseValue::Array(items) => {
+                // Postgres binary array wire format: ndim, has-null flag,
+                // element type OID, then one (dimension size, lower bound)
+                // pair per dimension, then each element as a length-prefixed
+                // blob (length -1 for NULL) in that element's own encoding.
+                let elem_oid = items
+                    .iter()
+                    .find_map(|item| item.produces())
+                    .and_then(|ty| ty.oid())
+                    .map(|oid| oid.0)
+                    .unwrap_or(25); // default to text if empty/unknown
+                let has_null = items.iter().any(|item| matches!(item, DatabaseValue::None));
+
+                buf.extend_from_slice(&1i32.to_be_bytes()); // ndim
+                buf.extend_from_slice(&(has_null as i32).to_be_bytes());
+                buf.extend_from_slice(&(elem_oid as i32).to_be_bytes());
+                buf.extend_from_slice(&(items.len() as i32).to_be_bytes()); // dimension size
+                buf.extend_from_slice(&1i32.to_be_bytes()); // lower bound
+
+                for item in items {
+                    if matches!(item, DatabaseValue::None) {
+                        buf.extend_from_slice(&(-1i32).to_be_bytes());
+                        continue;
+                    }
+                    let mut elem_buf = PgArgumentBuffer::default();
+                    let is_null = Encode::<Postgres>::encode_by_ref(item, &mut elem_buf)?;
+                    if matches!(is_null, IsNull::Yes) {
+                        buf.extend_from_slice(&(-1i32).to_be_bytes());
+                    } else {
+                        buf.extend_from_slice(&(elem_buf.len() as i32).to_be_bytes());
+                        buf.extend_from_slice(&elem_buf);
+                    }
+                }
+
+                Ok(IsNull::No)
+            }
+        }
+    }
+
+    fn produces(&self) -> Option<PgTypeInfo> {
+        Some(match self {
+            DatabaseValue::None => return None,
+            DatabaseValue::Str(_) | DatabaseValue::String(_) | DatabaseValue::Text(_) => {
+                PgTypeInfo::with_name("text")
+            }
+            DatabaseValue::Int(_) => PgTypeInfo::with_oid(sqlx::postgres::Oid(23)), // int4
+            DatabaseValue::Int64(_) => PgTypeInfo::with_oid(sqlx::postgres::Oid(20)), // int8
+            DatabaseValue::Float(_) => PgTypeInfo::with_oid(sqlx::postgres::Oid(701)), // float8
+            DatabaseValue::Boolean(_) => PgTypeInfo::with_oid(sqlx::postgres::Oid(16)), // bool
+            DatabaseValue::DateTime(_) => PgTypeInfo::with_oid(sqlx::postgres::Oid(1184)), // timestamptz
+            DatabaseValue::Array(items) => {
+                let elem_oid = items
+                    .iter()
+                    .find_map(|item| item.produces())
+                    .and_then(|ty| ty.oid())
+                    .map(|oid| oid.0)
+                    .unwrap_or(25);
+                PgTypeInfo::with_oid(sqlx::postgres::Oid(array_oid_for_element(elem_oid)))
+            }
+        })
+    }
+}
+
+/// Maps a scalar element type OID to the OID of its Postgres array type,
+/// e.g. `int8` (20) -> `_int8` (1016). Falls back to `_text` (1009) for any
+/// element type not covered here.
+fn array_oid_for_element(elem_oid: u32) -> u32 {
+    match elem_oid {
+        20 => 1016,   // _int8
+        23 => 1007,   // _int4
+        701 => 1022,  // _float8
+        16 => 1000,   // _bool
+        1184 => 1185, // _timestamptz
+        _ => 1009,    // _text
+    }
+}
+
+/// Implements decoding for PostgreSQL database operations, the read-side
+/// counterpart to the `Encode` impl above. Lets a `DatabaseValue` be read
+/// back out of a query result via `row.try_get::<DatabaseValue, _>(...)`.
+///
+/// The incoming column's OID picks which concrete type to decode through and
+/// which variant to land in; the decoded value is then `to_string()`-ed into
+/// that variant's `String` payload, so `DatabaseValue -> DB -> DatabaseValue`
+/// round-trips losslessly for every OID handled here. A SQL `NULL` decodes
+/// to `DatabaseValue::None` regardless of column type.
+impl<'r> Decode<'r, Postgres> for DatabaseValue {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        if value.is_null() {
+            return Ok(DatabaseValue::None);
+        }
+
+        let oid = value.type_info().oid().map(|oid| oid.0);
+        match oid {
+            // text, varchar, char, name
+            Some(25) | Some(1043) | Some(1042) | Some(19) => {
+                let s = <String as Decode<Postgres>>::decode(value)?;
+                Ok(DatabaseValue::String(s))
+            }
+            // int2, int4
+            Some(21) | Some(23) => {
+                let i = <i32 as Decode<Postgres>>::decode(value)?;
+                Ok(DatabaseValue::Int(i.to_string()))
+            }
+            // int8
+            Some(20) => {
+                let i = <i64 as Decode<Postgres>>::decode(value)?;
+                Ok(DatabaseValue::Int64(i.to_string()))
+            }
+            // float4, float8
+            Some(700) | Some(701) => {
+                let f = <f64 as Decode<Postgres>>::decode(value)?;
+                Ok(DatabaseValue::Float(f.to_string()))
+            }
+            // bool
+            Some(16) => {
+                let b = <bool as Decode<Postgres>>::decode(value)?;
+                Ok(DatabaseValue::Boolean(b.to_string()))
+            }
+            // timestamp, timestamptz
+            Some(1114) | Some(1184) => {
+                let dt = <OffsetDateTime as Decode<Postgres>>::decode(value)?;
+                Ok(DatabaseValue::DateTime(dt.format(&Iso8601::DEFAULT)?))
+            }
+            _ => Err(format!("unsupported Postgres OID {:?} for DatabaseValue", oid).into()),
         }
     }
 }
 
 /// Implements type information for PostgreSQL.
-/// All variants are encoded as text type for maximum flexibility.
+///
+/// `type_info()` stays the general text type so `DatabaseValue` can still be
+/// used where no column type is known ahead of time, but `produces()` above
+/// reports the variant's real type, and `compatible()` here accepts the OIDs
+/// of every type a variant can actually encode as, so binding against an
+/// `int8`, `float8`, `bool`, or `timestamptz` column doesn't require an
+/// implicit cast.
 impl Type<Postgres> for DatabaseValue {
-    fn type_info() -> sqlx::postgres::PgTypeInfo {
+    fn type_info() -> PgTypeInfo {
         // Most general type that can handle all our variants
-        sqlx::postgres::PgTypeInfo::with_name("text")
+        PgTypeInfo::with_name("text")
     }
 
-    fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
-        // OIDs for text-based types in PostgreSQL
+    fn compatible(ty: &PgTypeInfo) -> bool {
         let text_oids = [25, 1043, 1042, 19, 1042]; // text, varchar, char, name, bpchar
+        let numeric_oids = [23, 20, 701]; // int4, int8, float8
+        let bool_oids = [16]; // bool
+        let timestamp_oids = [1184, 1114]; // timestamptz, timestamp
+        let array_oids = [1009, 1016, 1007, 1022, 1000, 1185]; // _text, _int8, _int4, _float8, _bool, _timestamptz
         ty.oid()
-            .map(|oid| text_oids.contains(&oid.0))
+            .map(|oid| {
+                text_oids.contains(&oid.0)
+                    || numeric_oids.contains(&oid.0)
+                    || bool_oids.contains(&oid.0)
+                    || timestamp_oids.contains(&oid.0)
+                    || array_oids.contains(&oid.0)
+            })
             .unwrap_or(false)
     }
 }
 
+/// `Encode`/`Type` for MySQL and SQLite, feature-gated so a build that only
+/// enables `postgres` doesn't pull in the other two backends' types.
+///
+/// These are written as concrete per-backend impls rather than a single
+/// `impl<'q, DB: Database> Encode<'q, DB> for DatabaseValue` — a blanket impl
+/// over every `DB: Database` would overlap with the concrete
+/// `Encode<Postgres>`/`Type<Postgres>` impls above (Postgres itself satisfies
+/// `DB: Database`), which Rust rejects as conflicting implementations. Each
+/// variant still delegates to the same native type's own `Encode`/`Type` impl
+/// for that backend, so there's no per-OID bookkeeping to duplicate here.
+/// `Array` has no equivalent on either backend (no wire-level array type, and
+/// no generic way to fall back without picking a concrete encoding no caller
+/// asked for), so it's a hard error there instead of silently degrading.
+#[cfg(feature = "mysql")]
+mod mysql_support {
+    use super::DatabaseValue;
+    use sqlx::mysql::{MySql, MySqlTypeInfo};
+    use sqlx::{encode::IsNull, error::BoxDynError, Database, Encode, Type};
+    use std::str::FromStr;
+    use time::{format_description::well_known::Iso8601, OffsetDateTime};
+
+    impl<'q> Encode<'q, MySql> for DatabaseValue {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <MySql as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            match self {
+                DatabaseValue::None => Ok(IsNull::Yes),
+                DatabaseValue::Str(s) => Encode::<MySql>::encode_by_ref(s, buf),
+                DatabaseValue::String(s) => Encode::<MySql>::encode_by_ref(s, buf),
+                DatabaseValue::Text(s) => Encode::<MySql>::encode_by_ref(s, buf),
+                DatabaseValue::Int(i) => Encode::<MySql>::encode_by_ref(&i32::from_str(i)?, buf),
+                DatabaseValue::Int64(i) => Encode::<MySql>::encode_by_ref(&i64::from_str(i)?, buf),
+                DatabaseValue::Float(f) => Encode::<MySql>::encode_by_ref(&f64::from_str(f)?, buf),
+                DatabaseValue::Boolean(b) => {
+                    Encode::<MySql>::encode_by_ref(&bool::from_str(b)?, buf)
+                }
+                DatabaseValue::DateTime(dt) => {
+                    let parsed = OffsetDateTime::parse(dt, &Iso8601::DEFAULT)?;
+                    Encode::<MySql>::encode_by_ref(&parsed, buf)
+                }
+                DatabaseValue::Array(_) => {
+                    Err("DatabaseValue::Array has no MySQL equivalent".into())
+                }
+            }
+        }
+    }
+
+    impl Type<MySql> for DatabaseValue {
+        fn type_info() -> MySqlTypeInfo {
+            <String as Type<MySql>>::type_info()
+        }
+
+        fn compatible(ty: &MySqlTypeInfo) -> bool {
+            <String as Type<MySql>>::compatible(ty)
+                || <i64 as Type<MySql>>::compatible(ty)
+                || <f64 as Type<MySql>>::compatible(ty)
+                || <bool as Type<MySql>>::compatible(ty)
+                || <OffsetDateTime as Type<MySql>>::compatible(ty)
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite_support {
+    use super::DatabaseValue;
+    use sqlx::sqlite::{Sqlite, SqliteTypeInfo};
+    use sqlx::{encode::IsNull, error::BoxDynError, Database, Encode, Type};
+    use std::str::FromStr;
+    use time::{format_description::well_known::Iso8601, OffsetDateTime};
+
+    impl<'q> Encode<'q, Sqlite> for DatabaseValue {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <Sqlite as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            match self {
+                DatabaseValue::None => Ok(IsNull::Yes),
+                DatabaseValue::Str(s) => Encode::<Sqlite>::encode_by_ref(s, buf),
+                DatabaseValue::String(s) => Encode::<Sqlite>::encode_by_ref(s, buf),
+                DatabaseValue::Text(s) => Encode::<Sqlite>::encode_by_ref(s, buf),
+                DatabaseValue::Int(i) => Encode::<Sqlite>::encode_by_ref(&i32::from_str(i)?, buf),
+                DatabaseValue::Int64(i) => Encode::<Sqlite>::encode_by_ref(&i64::from_str(i)?, buf),
+                DatabaseValue::Float(f) => Encode::<Sqlite>::encode_by_ref(&f64::from_str(f)?, buf),
+                DatabaseValue::Boolean(b) => {
+                    Encode::<Sqlite>::encode_by_ref(&bool::from_str(b)?, buf)
+                }
+                DatabaseValue::DateTime(dt) => {
+                    let parsed = OffsetDateTime::parse(dt, &Iso8601::DEFAULT)?;
+                    Encode::<Sqlite>::encode_by_ref(&parsed, buf)
+                }
+                DatabaseValue::Array(_) => {
+                    Err("DatabaseValue::Array has no SQLite equivalent".into())
+                }
+            }
+        }
+    }
+
+    impl Type<Sqlite> for DatabaseValue {
+        fn type_info() -> SqliteTypeInfo {
+            <String as Type<Sqlite>>::type_info()
+        }
+
+        fn compatible(ty: &SqliteTypeInfo) -> bool {
+            <String as Type<Sqlite>>::compatible(ty)
+                || <i64 as Type<Sqlite>>::compatible(ty)
+                || <f64 as Type<Sqlite>>::compatible(ty)
+                || <bool as Type<Sqlite>>::compatible(ty)
+                || <OffsetDateTime as Type<Sqlite>>::compatible(ty)
+        }
+    }
+}
+
 /// Collection of FromIterator implementations to allow convenient conversion
 /// from iterators of various types into DatabaseValue.
 /// These implementations enable collecting iterators directly into DatabaseValue.
@@ -110,37 +377,51 @@ impl<'a> FromIterator<&'a String> for DatabaseValue {
 }
 
 impl FromIterator<bool> for DatabaseValue {
-    /// Collects an iterator of booleans into a DatabaseValue::Boolean
-    /// Each boolean is converted to its string representation
+    /// Collects an iterator of booleans into a `DatabaseValue::Array` of
+    /// `Boolean`s, binding as a real `bool[]` rather than a concatenated string.
     fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
-        DatabaseValue::Boolean(iter.into_iter().map(|b| b.to_string()).collect())
+        DatabaseValue::Array(
+            iter.into_iter()
+                .map(|b| DatabaseValue::Boolean(b.to_string()))
+                .collect(),
+        )
     }
 }
 
 impl FromIterator<OffsetDateTime> for DatabaseValue {
-    /// Collects an iterator of OffsetDateTime into a DatabaseValue::DateTime
-    /// Each datetime is formatted according to ISO 8601 standard
+    /// Collects an iterator of OffsetDateTime into a `DatabaseValue::Array` of
+    /// `DateTime`s, binding as a real `timestamptz[]` rather than a
+    /// concatenated string.
     fn from_iter<I: IntoIterator<Item = OffsetDateTime>>(iter: I) -> Self {
-        DatabaseValue::DateTime(
+        DatabaseValue::Array(
             iter.into_iter()
-                .map(|dt| dt.format(&Iso8601::DEFAULT).unwrap())
+                .map(|dt| DatabaseValue::DateTime(dt.format(&Iso8601::DEFAULT).unwrap()))
                 .collect(),
         )
     }
 }
 
 impl FromIterator<i64> for DatabaseValue {
-    /// Collects an iterator of 64-bit integers into a DatabaseValue::Int64
-    /// Each integer is converted to its string representation
+    /// Collects an iterator of 64-bit integers into a `DatabaseValue::Array`
+    /// of `Int64`s, binding as a real `int8[]` rather than a concatenated string.
     fn from_iter<I: IntoIterator<Item = i64>>(iter: I) -> Self {
-        DatabaseValue::Int64(iter.into_iter().map(|i| i.to_string()).collect())
+        DatabaseValue::Array(
+            iter.into_iter()
+                .map(|i| DatabaseValue::Int64(i.to_string()))
+                .collect(),
+        )
     }
 }
 
 impl FromIterator<f64> for DatabaseValue {
-    /// Collects an iterator of floating-point numbers into a DatabaseValue::Float
-    /// Each number is converted to its string representation
+    /// Collects an iterator of floating-point numbers into a
+    /// `DatabaseValue::Array` of `Float`s, binding as a real `float8[]`
+    /// rather than a concatenated string.
     fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
-        DatabaseValue::Float(iter.into_iter().map(|f| f.to_string()).collect())
+        DatabaseValue::Array(
+            iter.into_iter()
+                .map(|f| DatabaseValue::Float(f.to_string()))
+                .collect(),
+        )
     }
 }
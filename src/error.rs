@@ -0,0 +1,52 @@
+//! Crate-wide error type for database operations.
+//!
+//! The database macros used to propagate `sqlx::Error` directly, `.unwrap()`
+//! row conversions, or wrap failures in an ad-hoc `anyhow::Error`. `DatabaseError`
+//! replaces all of that with a single structured enum so callers can match on
+//! what actually went wrong (a bad row, a misconfigured pool, an archive attempt
+//! on a non-archivable resource) instead of a panic or an opaque `anyhow::Error`.
+
+use std::fmt;
+
+#[derive(Debug)]
+pub enum DatabaseError {
+    /// A query failed at the database layer (connection, syntax, constraint, etc.).
+    Sqlx(sqlx::Error),
+    /// A row was fetched successfully but could not be converted into its resource type.
+    RowConversion(String),
+    /// `archive_resource!`/`restore_resource!` was called on a resource whose
+    /// `DatabaseResource::is_archivable()` returns `false`.
+    NotArchivable(String),
+    /// `init_connection` was called more than once.
+    PoolAlreadyInitialized,
+    /// A request-scoped `DbConn` transaction failed to open.
+    TransactionFailed(String),
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseError::Sqlx(e) => write!(f, "database error: {}", e),
+            DatabaseError::RowConversion(message) => {
+                write!(f, "failed to convert row: {}", message)
+            }
+            DatabaseError::NotArchivable(resource) => {
+                write!(f, "{} is not archivable", resource)
+            }
+            DatabaseError::PoolAlreadyInitialized => {
+                write!(f, "connection pool already initialized")
+            }
+            DatabaseError::TransactionFailed(message) => {
+                write!(f, "failed to open transaction: {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+impl From<sqlx::Error> for DatabaseError {
+    fn from(error: sqlx::Error) -> Self {
+        DatabaseError::Sqlx(error)
+    }
+}
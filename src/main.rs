@@ -1,19 +1,57 @@
-use std::sync::{Arc, Mutex};
-use tokio::sync::broadcast;
+use crate::api::token::{validate_token, RawToken};
+use std::collections::HashMap;
 use warp::Filter;
 
 mod websockets;
 
+/// Marks a `/ws` upgrade rejected for a missing or invalid token, so warp's
+/// rejection handling reports it as `401 Unauthorized` instead of falling
+/// through to a generic 404/500.
+#[derive(Debug)]
+struct Unauthorized;
+impl warp::reject::Reject for Unauthorized {}
+
+/// Extracts and validates the token for a `/ws` upgrade.
+///
+/// A browser can't set an `Authorization` header on a WebSocket handshake,
+/// so the token travels as the `Sec-WebSocket-Protocol` value — the same
+/// workaround `websocket::WsUser` uses for the Rocket-side `/ws` endpoint —
+/// falling back to a `?token=` query parameter. Returns the authenticated
+/// `user_id` on success, which `websockets::handle_connection` then uses to
+/// scope delivery; a missing, invalid, or expired token rejects the upgrade
+/// before it ever reaches the handler.
+async fn ws_auth(
+    protocol: Option<String>,
+    query: HashMap<String, String>,
+) -> Result<String, warp::Rejection> {
+    let raw_value = protocol
+        .map(|value| value.trim().to_string())
+        .or_else(|| query.get("token").cloned());
+
+    let raw_value = match raw_value {
+        Some(value) if !value.is_empty() => value,
+        _ => return Err(warp::reject::custom(Unauthorized)),
+    };
+
+    match validate_token(RawToken { value: raw_value }).await {
+        Ok(token) => Ok(token.user_id),
+        Err(_) => Err(warp::reject::custom(Unauthorized)),
+    }
+}
+
 #[tokio::main]
 async fn main() {
-    let tx = Arc::new(Mutex::new(broadcast::channel(100).0));
-    let tx_ws = tx.clone();
     let ws_route = warp::path("ws")
         .and(warp::ws())
-        .map(move |ws: warp::ws::Ws| {
+        .and(warp::header::optional::<String>("sec-websocket-protocol"))
+        .and(warp::query::<HashMap<String, String>>())
+        .and_then(|ws: warp::ws::Ws, protocol: Option<String>, query: HashMap<String, String>| async move {
+            let user_id = ws_auth(protocol, query).await?;
+            Ok::<_, warp::Rejection>((ws, user_id))
+        })
+        .map(|(ws, user_id): (warp::ws::Ws, String)| {
             println!("WebSocket connection established");
-            let tx = tx_ws.clone();
-            ws.on_upgrade(move |websocket| websockets::handle_connection(websocket, tx))
+            ws.on_upgrade(move |socket| websockets::handle_connection(socket, user_id))
         });
     warp::serve(ws_route).run(([0, 0, 0, 0], 8000)).await;
 }
@@ -14,6 +14,28 @@ pub enum AuthenticationError {
     SessionNotFound,
     InvalidToken,
     TokenExpired,
+    /// An already-rotated refresh token was presented again to `utils::oauth::refresh`.
+    /// This is the standard signal that a refresh token was stolen and replayed after
+    /// the legitimate client already rotated past it, so the whole token family
+    /// (every access/refresh token sharing its `family_id`) is revoked in response.
+    TokenReuseDetected,
+    /// The account's `blocked` flag is set. Returned from `login`/`reset_password`
+    /// before password verification, so a blocked account can't be used to probe
+    /// for a valid password either.
+    BlockedUser,
+    /// `reset_password` was called with neither `code` nor `resetToken` set.
+    MissingRecoveryMethod,
+    /// `verify_two_factor` was called for an account with TOTP disabled.
+    TwoFactorNotEnabled,
+    /// Neither the submitted TOTP code nor a backup code verified.
+    InvalidTwoFactorCode,
+    /// `login` was rejected before password verification because the
+    /// username or IP has too many recent failed attempts; see
+    /// `utils::login_throttle`.
+    TooManyAttempts,
+    /// The caller's account doesn't carry the `role` an endpoint requires,
+    /// e.g. a non-admin account calling `api::authentications::toggle_blocked`.
+    NotAuthorized,
 }
 
 impl std::fmt::Display for AuthenticationError {
@@ -27,12 +49,34 @@ impl std::fmt::Display for AuthenticationError {
             AuthenticationError::SessionNotFound => write!(f, "Session not found"),
             AuthenticationError::InvalidToken => write!(f, "Invalid token"),
             AuthenticationError::TokenExpired => write!(f, "Token expired"),
+            AuthenticationError::TokenReuseDetected => write!(f, "Token reuse detected"),
+            AuthenticationError::BlockedUser => write!(f, "Account is blocked"),
+            AuthenticationError::MissingRecoveryMethod => {
+                write!(f, "A backup code or reset token is required")
+            }
+            AuthenticationError::TwoFactorNotEnabled => {
+                write!(f, "Two-factor authentication is not enabled for this account")
+            }
+            AuthenticationError::InvalidTwoFactorCode => {
+                write!(f, "Invalid two-factor authentication code")
+            }
+            AuthenticationError::TooManyAttempts => {
+                write!(f, "Too many failed login attempts, try again later")
+            }
+            AuthenticationError::NotAuthorized => {
+                write!(f, "You do not have permission to perform this action")
+            }
         }
     }
 }
 
 impl std::error::Error for AuthenticationError {}
 
+/// A single device/browser session. A user may have any number of these
+/// concurrently — one per device — unlike the single shared row this table
+/// used to hold; see `api::authentications::issue_session`,
+/// `api::authentications::list_sessions`, and
+/// `api::authentications::revoke_session`.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Authentication {
@@ -40,6 +84,24 @@ pub struct Authentication {
     pub user_id: String,
     pub token: String,
 
+    /// A human-readable label for the device/client this session belongs
+    /// to, e.g. `"Chrome on macOS"`. Supplied by the client at login; `None`
+    /// for sessions issued before this field existed.
+    pub device_label: Option<String>,
+    /// The `User-Agent` header captured at session issuance, for display
+    /// alongside `device_label` in a session list.
+    pub user_agent: Option<String>,
+    /// The client IP captured at session issuance.
+    pub ip: Option<String>,
+
+    #[serde(
+        serialize_with = "serialize_offset_date_time",
+        deserialize_with = "deserialize_offset_date_time"
+    )]
+    /// When this session last issued or rotated a token. Set at creation and
+    /// on every `refresh`, so a session list can be sorted by recency.
+    pub last_seen: Option<OffsetDateTime>,
+
     #[serde(
         serialize_with = "serialize_offset_date_time",
         deserialize_with = "deserialize_offset_date_time"
@@ -71,6 +133,10 @@ impl DatabaseResource for Authentication {
             id: row.get("id"),
             user_id: row.get("user_id"),
             token: row.get("token"),
+            device_label: row.get("device_label"),
+            user_agent: row.get("user_agent"),
+            ip: row.get("ip"),
+            last_seen: row.get("last_seen"),
             expires_at: row.get("expires_at"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
@@ -83,7 +149,12 @@ impl DatabaseResource for Authentication {
     }
 
     fn is_archivable() -> bool {
-        false
+        // A rotated-away refresh token is archived rather than hard-deleted,
+        // the same way `OauthToken` archives on rotation (see
+        // `utils::oauth::refresh`) — a second presentation of an archived
+        // row is the standard signal that the refresh token was stolen and
+        // replayed after the legitimate client already rotated past it.
+        true
     }
 
     fn is_updatable() -> bool {
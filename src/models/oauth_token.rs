@@ -0,0 +1,120 @@
+use crate::database::traits::DatabaseResource;
+use crate::utils::time::{deserialize_offset_date_time, serialize_offset_date_time};
+use rocket::serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, Error, Row};
+use std::fmt;
+use time::OffsetDateTime;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum OauthTokenError {
+    OauthTokenCreationFailed,
+    OauthTokenUpdateFailed,
+    OauthTokenDeletionFailed,
+    OauthTokenNotFound,
+}
+
+impl fmt::Display for OauthTokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OauthTokenError::OauthTokenCreationFailed => write!(f, "OAuth token creation failed"),
+            OauthTokenError::OauthTokenUpdateFailed => write!(f, "OAuth token update failed"),
+            OauthTokenError::OauthTokenDeletionFailed => write!(f, "OAuth token deletion failed"),
+            OauthTokenError::OauthTokenNotFound => write!(f, "OAuth token not found"),
+        }
+    }
+}
+
+impl std::error::Error for OauthTokenError {}
+
+/// A single issued OAuth2 access or refresh token.
+///
+/// Access and refresh tokens share this table, distinguished by `token_type`
+/// (`"access"` or `"refresh"`). Every pair issued together (see
+/// `utils::oauth::issue`) shares a `family_id`, so a refresh token's
+/// replacement lineage can be revoked as a unit if reuse of an already-rotated
+/// token is detected — see `utils::oauth::refresh`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OauthToken {
+    pub id: Option<String>,
+    pub user_id: Option<String>,
+    pub client_id: Option<String>,
+    pub family_id: Option<String>,
+    pub token_value: Option<String>,
+    pub token_type: Option<String>,
+    /// Space-separated scopes, e.g. `"alerts:read alerts:write"`.
+    pub scope: Option<String>,
+
+    #[serde(
+        serialize_with = "serialize_offset_date_time",
+        deserialize_with = "deserialize_offset_date_time"
+    )]
+    pub expires_at: Option<OffsetDateTime>,
+
+    #[serde(
+        serialize_with = "serialize_offset_date_time",
+        deserialize_with = "deserialize_offset_date_time"
+    )]
+    pub created_at: Option<OffsetDateTime>,
+
+    #[serde(
+        serialize_with = "serialize_offset_date_time",
+        deserialize_with = "deserialize_offset_date_time"
+    )]
+    pub archived_at: Option<OffsetDateTime>,
+}
+
+impl DatabaseResource for OauthToken {
+    fn from_row(row: &PgRow) -> Result<Self, Error> {
+        Ok(OauthToken {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            client_id: row.get("client_id"),
+            family_id: row.get("family_id"),
+            token_value: row.get("token_value"),
+            token_type: row.get("token_type"),
+            scope: row.get("scope"),
+            expires_at: row.get("expires_at"),
+            created_at: row.get("created_at"),
+            archived_at: row.get("archived_at"),
+        })
+    }
+
+    fn has_id() -> bool {
+        true
+    }
+
+    fn is_archivable() -> bool {
+        true
+    }
+
+    fn is_updatable() -> bool {
+        false
+    }
+
+    fn is_creatable() -> bool {
+        true
+    }
+
+    fn is_expirable() -> bool {
+        // Access and refresh tokens need different lifetimes, so
+        // `utils::oauth` sets `expires_at` explicitly on insert rather than
+        // relying on the macros' fixed 30-day default.
+        false
+    }
+
+    fn is_verifiable() -> bool {
+        true
+    }
+}
+
+impl OauthToken {
+    /// Whether this token's space-separated `scope` includes `required` —
+    /// for guards that need to gate a route behind a specific OAuth scope.
+    pub fn has_scope(&self, required: &str) -> bool {
+        self.scope
+            .as_deref()
+            .map(|scope| scope.split_whitespace().any(|granted| granted == required))
+            .unwrap_or(false)
+    }
+}
@@ -0,0 +1,120 @@
+use crate::database::traits::DatabaseResource;
+use crate::utils::time::{deserialize_offset_date_time, serialize_offset_date_time};
+use rocket::serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, Error, Row};
+use std::fmt;
+use time::OffsetDateTime;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum PasswordResetTokenError {
+    TokenNotFound,
+    TokenExpired,
+    TokenAlreadyUsed,
+    TokenCreationFailed,
+    TokenUpdateFailed,
+}
+
+impl fmt::Display for PasswordResetTokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordResetTokenError::TokenNotFound => write!(f, "Password reset token not found"),
+            PasswordResetTokenError::TokenExpired => write!(f, "Password reset token expired"),
+            PasswordResetTokenError::TokenAlreadyUsed => {
+                write!(f, "Password reset token already used")
+            }
+            PasswordResetTokenError::TokenCreationFailed => {
+                write!(f, "Password reset token creation failed")
+            }
+            PasswordResetTokenError::TokenUpdateFailed => {
+                write!(f, "Password reset token update failed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PasswordResetTokenError {}
+
+/// A single-use password reset token, issued by `utils::password_reset::request_reset`
+/// and redeemed by `api::authentications::reset_password`.
+///
+/// Only the SHA-256 hash of the raw token is ever stored (see
+/// `utils::password_reset::hash_token`) — the same high-entropy,
+/// equality-lookup rationale as `Authentication`'s refresh tokens, see
+/// `api::authentications::hash_refresh_token` — so a leaked database can't
+/// be used to mint working reset links. A user has at most one live token at
+/// a time; requesting a new one overwrites it in place.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordResetToken {
+    pub id: Option<String>,
+    pub user_id: Option<String>,
+    pub token: Option<String>,
+    pub used: Option<bool>,
+
+    #[serde(
+        serialize_with = "serialize_offset_date_time",
+        deserialize_with = "deserialize_offset_date_time"
+    )]
+    pub expires_at: Option<OffsetDateTime>,
+
+    #[serde(
+        serialize_with = "serialize_offset_date_time",
+        deserialize_with = "deserialize_offset_date_time"
+    )]
+    pub created_at: Option<OffsetDateTime>,
+
+    #[serde(
+        serialize_with = "serialize_offset_date_time",
+        deserialize_with = "deserialize_offset_date_time"
+    )]
+    pub updated_at: Option<OffsetDateTime>,
+
+    #[serde(
+        serialize_with = "serialize_offset_date_time",
+        deserialize_with = "deserialize_offset_date_time"
+    )]
+    pub archived_at: Option<OffsetDateTime>,
+}
+
+impl DatabaseResource for PasswordResetToken {
+    fn from_row(row: &PgRow) -> Result<Self, Error> {
+        Ok(PasswordResetToken {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            token: row.get("token"),
+            used: row.get("used"),
+            expires_at: row.get("expires_at"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            archived_at: row.get("archived_at"),
+        })
+    }
+
+    fn has_id() -> bool {
+        true
+    }
+
+    fn is_archivable() -> bool {
+        false
+    }
+
+    fn is_updatable() -> bool {
+        true
+    }
+
+    fn is_creatable() -> bool {
+        true
+    }
+
+    fn is_expirable() -> bool {
+        // A reset token's 1-hour lifetime is far shorter than the macros'
+        // fixed 30-day default, so `expires_at` is set explicitly on
+        // insert/update (see `utils::password_reset::request_reset`)
+        // instead — the same reason `OauthToken` keeps this `false`.
+        false
+    }
+
+    fn is_verifiable() -> bool {
+        false
+    }
+}
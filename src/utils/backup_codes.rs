@@ -1,72 +1,49 @@
 //! Backup code generation and management utilities.
 //!
 //! This module provides functionality for generating secure backup codes that can be
-//! used as a fallback authentication method. The codes are generated using a
-//! combination of random numbers and timestamps to ensure uniqueness, and are then
+//! used as a fallback authentication method. Codes are drawn straight from a CSPRNG
+//! with enough entropy that collisions are not a practical concern, and are then
 //! hashed for security.
 //!
-//! The generated codes are guaranteed to be unique within the database, with automatic
-//! regeneration if a collision occurs.
+//! Codes are the recovery fallback for `utils::totp`, the primary second factor:
+//! [`generate_and_store_backup_codes`] stores only the Argon2 hash of each code,
+//! returning the plaintext to the caller exactly once, and [`verify_backup_code`]
+//! checks a submitted code against a user's stored hashes without ever needing
+//! the plaintext again.
 
-use rand::{rng, Rng};
-use sha2::{Digest, Sha256};
-use time::OffsetDateTime;
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use base32::Alphabet;
+use rand::RngCore;
 
+use crate::database::values::DatabaseValue;
 use crate::find_all_resources_where_fields;
-use crate::models::user_backup_code::UserBackupCode;
+use crate::insert_resource;
+use crate::models::user_backup_code::{UserBackupCode, UserBackupCodeError};
+use crate::update_resource;
 
-/// Generates a single backup code using a cryptographically secure process.
+/// Generates a single backup code with 80 bits of CSPRNG entropy: 10 bytes
+/// from `OsRng`, base32-encoded (RFC 4648, no padding) into 16 characters and
+/// split into two groups of 8 for readability, e.g. `XHQ3MKZP-7AF2RNQC`.
 ///
-/// The code generation process:
-/// 1. Generates a random 6-digit number
-/// 2. Combines it with the current timestamp
-/// 3. Creates a SHA-256 hash of the combination
-/// 4. Takes the first 7 bytes of the hash and converts them to hexadecimal
-///
-/// # Returns
-/// A String containing the generated backup code in hexadecimal format
+/// At 80 bits, a collision against any existing code is astronomically
+/// unlikely, so unlike the old timestamp-derived codes this doesn't need a
+/// database round-trip to check for one.
 fn generate_code() -> String {
-    let mut rng = rng();
-    let timestamp = OffsetDateTime::now_utc().unix_timestamp();
-    let code = format!("{:06}", rng.random_range(0..1000000));
-    let hash = Sha256::digest(format!("{:?}{}", timestamp, code).as_bytes()).to_vec();
-    hash[..7]
-        .iter()
-        .map(|b| format!("{:02x}", b))
-        .collect::<Vec<String>>()
-        .join("")
+    let mut bytes = [0u8; 10];
+    OsRng.fill_bytes(&mut bytes);
+    let encoded = base32::encode(Alphabet::Rfc4648 { padding: false }, &bytes);
+    format!("{}-{}", &encoded[..8], &encoded[8..])
 }
 
-/// Generates a unique backup code and ensures it doesn't exist in the database.
-///
-/// This function will recursively generate new codes until it finds one that
-/// doesn't already exist in the database.
+/// Generates a single backup code.
 ///
 /// # Returns
-/// A String containing a unique backup code
-///
-/// # Note
-/// If a database error occurs, the function will recursively try again
+/// A String containing a backup code with 80 bits of entropy
 pub async fn generate_backup_code() -> String {
-    let backup_code = generate_code();
-    match find_all_resources_where_fields!(
-        UserBackupCode,
-        vec![("code", DatabaseValue::String(backup_code.clone()))]
-    )
-    .await
-    {
-        Ok(backup_codes) => {
-            if backup_codes.is_empty() {
-                backup_code
-            } else {
-                Box::pin(generate_backup_code()).await
-            }
-        }
-        Err(err) => {
-            println!("Error generating backup code: {:?}", err);
-            Box::pin(generate_backup_code()).await
-        }
-    }
+    generate_code()
 }
 
 /// Generates a set of 10 unique backup codes.
@@ -94,3 +71,94 @@ pub async fn generate_backup_codes() -> Vec<String> {
     }
     codes
 }
+
+/// Hashes a single plaintext backup code with Argon2, using a fresh random salt.
+fn hash_backup_code(code: &str) -> Result<String, UserBackupCodeError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(code.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|_| UserBackupCodeError::CodeCreationFailed)
+}
+
+/// Generates a fresh set of backup codes for `user_id` and stores only their
+/// Argon2 hashes, one row per code.
+///
+/// Returns the plaintext codes to the caller — this is the only time they're
+/// ever available outside of this call, so the caller must display them to
+/// the user immediately; they can't be recovered from the database afterward.
+pub async fn generate_and_store_backup_codes(
+    user_id: &str,
+) -> Result<Vec<String>, UserBackupCodeError> {
+    let plaintext_codes = generate_backup_codes().await;
+
+    for code in &plaintext_codes {
+        let hashed = hash_backup_code(code)?;
+        let params = vec![
+            ("user_id", DatabaseValue::String(user_id.to_string())),
+            ("code", DatabaseValue::String(hashed)),
+            ("used", DatabaseValue::Boolean(false.to_string())),
+        ];
+        insert_resource!(UserBackupCode, params)
+            .await
+            .map_err(|_| UserBackupCodeError::CodeCreationFailed)?;
+    }
+
+    Ok(plaintext_codes)
+}
+
+/// Verifies `submitted_code` against `user_id`'s stored backup codes.
+///
+/// Every stored hash has its own random salt, so the submitted code has to be
+/// checked against each of the user's rows in turn rather than looked up
+/// directly. On a match: `CodeAlreadyUsed` if that code was already redeemed,
+/// `CodeExpired` if it was archived before being redeemed, otherwise the row
+/// is marked `used = true` and verification succeeds. `CodeNotFound` covers
+/// both "no rows for this user" and "no row's hash matches".
+pub async fn verify_backup_code(
+    user_id: &str,
+    submitted_code: &str,
+) -> Result<(), UserBackupCodeError> {
+    let params = vec![("user_id", DatabaseValue::String(user_id.to_string()))];
+    // include_archived: true, since an already-archived code still needs to
+    // be found here to distinguish CodeExpired from CodeNotFound below.
+    let codes: Vec<UserBackupCode> =
+        find_all_resources_where_fields!(UserBackupCode, params, true)
+            .await
+            .map_err(|_| UserBackupCodeError::CodeNotFound)?;
+
+    let matching = codes.iter().find(|stored| {
+        stored
+            .code
+            .as_deref()
+            .and_then(|hash| PasswordHash::new(hash).ok())
+            .map(|parsed_hash| {
+                Argon2::default()
+                    .verify_password(submitted_code.as_bytes(), &parsed_hash)
+                    .is_ok()
+            })
+            .unwrap_or(false)
+    });
+
+    let Some(stored) = matching else {
+        return Err(UserBackupCodeError::CodeNotFound);
+    };
+
+    if stored.archived_at.is_some() {
+        return Err(UserBackupCodeError::CodeExpired);
+    }
+    if stored.used == Some(true) {
+        return Err(UserBackupCodeError::CodeAlreadyUsed);
+    }
+
+    let id = stored
+        .id
+        .clone()
+        .ok_or(UserBackupCodeError::CodeVerificationFailed)?;
+    let update_params = vec![("used", DatabaseValue::Boolean(true.to_string()))];
+    update_resource!(UserBackupCode, id, update_params)
+        .await
+        .map_err(|_| UserBackupCodeError::CodeUpdateFailed)?;
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+//! Stateless JWT signing and verification.
+//!
+//! `VerifiedToken::from_raw` used to hit the database on every authenticated
+//! request just to resolve a token to a user ID. This module lets callers
+//! issue self-contained, signed tokens instead: `base64url(header).base64url(payload).base64url(sig)`,
+//! where `payload` is `{ "sub", "iat", "exp", "stamp" }` and `sig` is an
+//! `HMAC-SHA256` over the first two segments. Verifying one of these tokens
+//! needs only the signing secret and one indexed lookup of the user's
+//! current `security_stamp` (see `utils::security_stamp`) — never a full
+//! session-table round-trip.
+//!
+//! Opaque, database-backed tokens (UUIDs stored in `Authentication`) remain
+//! fully supported side by side with this mode: `verify` only ever rejects
+//! a string as [`JwtVerifyError::Malformed`] when it isn't a well-formed,
+//! correctly-signed JWT, so callers can fall back to the existing DB lookup.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use time::{Duration, OffsetDateTime};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The JWT header this module issues. Always the same, so it's encoded as a constant.
+const HEADER: &str = r#"{"alg":"HS256","typ":"JWT"}"#;
+
+/// The claims carried by a token issued by [`sign`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    /// Subject — the user ID the token authenticates as.
+    pub sub: String,
+    /// Issued-at, Unix seconds.
+    pub iat: i64,
+    /// Expiry, Unix seconds.
+    pub exp: i64,
+    /// The user's `security_stamp` at the time this token was issued. Checked
+    /// against their current stamp on every verification (see
+    /// `utils::security_stamp`); rotating the stamp on a password or 2FA
+    /// change makes every previously issued token fail this check at once.
+    /// Not a secret in its own right — the JWT payload isn't encrypted, only
+    /// signed — it's a revocation nonce, not sensitive data.
+    pub stamp: String,
+}
+
+/// Why `verify` rejected a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwtVerifyError {
+    /// Not three base64url segments, not valid JSON, or the signature doesn't
+    /// match. Indistinguishable from "this is an opaque DB token, not a JWT".
+    Malformed,
+    /// The signature checked out but `exp <= now`.
+    Expired,
+}
+
+/// Reads the HMAC signing secret from Rocket config (`jwt_secret`), falling
+/// back to a fixed development secret so the app keeps working out of the
+/// box. Deployments must set `jwt_secret` (e.g. via `Rocket.toml` or the
+/// `ROCKET_JWT_SECRET` environment variable) to anything else in production.
+fn signing_secret() -> String {
+    rocket::Config::figment()
+        .extract_inner::<String>("jwt_secret")
+        .unwrap_or_else(|_| "alertrs-dev-secret".to_string())
+}
+
+fn hmac(secret: &str, signing_input: &str) -> Vec<u8> {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(signing_input.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Compares two byte slices in constant time with respect to their contents,
+/// to avoid leaking the signature byte-by-byte through timing.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Signs a token for `user_id` that expires after `ttl`, embedding
+/// `security_stamp` so the token can be mass-revoked later by rotating it.
+///
+/// # Example
+/// ```rust
+/// let token = sign("user-123", &security_stamp, Duration::days(30));
+/// ```
+pub fn sign(user_id: &str, security_stamp: &str, ttl: Duration) -> String {
+    let now = OffsetDateTime::now_utc();
+    let claims = Claims {
+        sub: user_id.to_string(),
+        iat: now.unix_timestamp(),
+        exp: (now + ttl).unix_timestamp(),
+        stamp: security_stamp.to_string(),
+    };
+    let header_b64 = URL_SAFE_NO_PAD.encode(HEADER);
+    let payload_b64 = URL_SAFE_NO_PAD.encode(
+        serde_json::to_string(&claims).expect("Claims always serializes"),
+    );
+    let signing_input = format!("{}.{}", header_b64, payload_b64);
+    let sig_b64 = URL_SAFE_NO_PAD.encode(hmac(&signing_secret(), &signing_input));
+    format!("{}.{}", signing_input, sig_b64)
+}
+
+/// Verifies a token produced by [`sign`] without touching the database.
+///
+/// Splits `token` on `.`, recomputes the HMAC over the header and payload
+/// segments, compares it against the decoded signature in constant time,
+/// then parses and checks `exp`. Returns [`JwtVerifyError::Malformed`] for
+/// anything that isn't a well-formed, correctly-signed JWT — including
+/// opaque DB-backed tokens, which aren't JWTs at all — so callers can fall
+/// back to the existing lookup path.
+pub fn verify(token: &str) -> Result<Claims, JwtVerifyError> {
+    let mut parts = token.split('.');
+    let (header_b64, payload_b64, sig_b64) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(h), Some(p), Some(s), None) => (h, p, s),
+        _ => return Err(JwtVerifyError::Malformed),
+    };
+
+    let signing_input = format!("{}.{}", header_b64, payload_b64);
+    let expected_sig = hmac(&signing_secret(), &signing_input);
+    let actual_sig = URL_SAFE_NO_PAD
+        .decode(sig_b64)
+        .map_err(|_| JwtVerifyError::Malformed)?;
+    if !constant_time_eq(&expected_sig, &actual_sig) {
+        return Err(JwtVerifyError::Malformed);
+    }
+
+    let payload = URL_SAFE_NO_PAD
+        .decode(payload_b64)
+        .map_err(|_| JwtVerifyError::Malformed)?;
+    let claims: Claims =
+        serde_json::from_slice(&payload).map_err(|_| JwtVerifyError::Malformed)?;
+
+    if claims.exp <= OffsetDateTime::now_utc().unix_timestamp() {
+        return Err(JwtVerifyError::Expired);
+    }
+
+    Ok(claims)
+}
@@ -0,0 +1,220 @@
+//! In-memory brute-force protection for `api::authentications::login`.
+//!
+//! Failed attempts are tracked per-username and per-IP in a process-wide
+//! registry (mirroring `websocket`'s per-user channel registry), rather than
+//! a database table — a login attempt counter doesn't need to survive a
+//! restart or be queried, and persisting it would mean a DB round-trip on
+//! every request from exactly the traffic this is meant to cut off before it
+//! reaches the database at all. `login` calls [`check`] before password
+//! verification, then [`record_failure`] or [`record_success`] afterward.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use time::{Duration, OffsetDateTime};
+
+/// Failed attempts within this span count toward `THRESHOLD`; anything older
+/// has fallen out of the sliding window.
+const WINDOW: Duration = Duration::minutes(15);
+/// Failures allowed within `WINDOW` before the first lockout kicks in.
+const THRESHOLD: u32 = 5;
+/// Lockout duration for the first breach of `THRESHOLD`; doubles for every
+/// further multiple of `THRESHOLD` failures (5 -> 1 min, 10 -> 2 min, 15 -> 4
+/// min, ...).
+const BASE_LOCKOUT: Duration = Duration::minutes(1);
+/// A key with no failures for this long is dropped on its next lookup, so
+/// the registry doesn't grow unbounded over the life of the process.
+const ENTRY_TTL: Duration = Duration::hours(1);
+
+#[derive(Debug, Clone, Default)]
+struct AttemptRecord {
+    /// Timestamps of failures still inside `WINDOW` as of the last prune.
+    failures: Vec<OffsetDateTime>,
+    /// Set once `failures.len()` crosses `THRESHOLD`; cleared on success.
+    locked_until: Option<OffsetDateTime>,
+}
+
+impl AttemptRecord {
+    fn prune(&mut self, now: OffsetDateTime) {
+        self.failures.retain(|at| now - *at < WINDOW);
+    }
+
+    /// The most recent activity on this record, lockout included, for
+    /// eviction purposes — a record can be locked out well past its last
+    /// failure falling out of `WINDOW`.
+    fn last_activity(&self) -> Option<OffsetDateTime> {
+        match (self.failures.last().copied(), self.locked_until) {
+            (Some(last), Some(until)) => Some(last.max(until)),
+            (Some(last), None) => Some(last),
+            (None, Some(until)) => Some(until),
+            (None, None) => None,
+        }
+    }
+}
+
+static REGISTRY: OnceLock<Mutex<HashMap<String, AttemptRecord>>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<HashMap<String, AttemptRecord>> {
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn username_key(username: &str) -> String {
+    format!("user:{username}")
+}
+
+fn ip_key(ip: &str) -> String {
+    format!("ip:{ip}")
+}
+
+/// Drops any entry whose last activity (failure or lockout) is older than
+/// `ENTRY_TTL`.
+fn evict_stale(registry: &mut HashMap<String, AttemptRecord>, now: OffsetDateTime) {
+    registry.retain(|_, record| {
+        record
+            .last_activity()
+            .map(|last| now - last < ENTRY_TTL)
+            .unwrap_or(false)
+    });
+}
+
+/// If `key` is currently locked out, returns how much longer it has left.
+fn remaining_lockout(
+    registry: &mut HashMap<String, AttemptRecord>,
+    key: &str,
+    now: OffsetDateTime,
+) -> Option<Duration> {
+    let record = registry.get_mut(key)?;
+    record.prune(now);
+    match record.locked_until {
+        Some(until) if until > now => Some(until - now),
+        _ => None,
+    }
+}
+
+/// Checks whether `username` or `ip` is currently locked out. Returns the
+/// longer of the two remaining lockouts (so a single `Retry-After` covers
+/// both), or `None` if neither is blocked. Call this before touching the
+/// database or verifying a password.
+pub fn check(username: &str, ip: Option<&str>) -> Option<Duration> {
+    let now = OffsetDateTime::now_utc();
+    let mut registry = registry().lock().unwrap();
+    evict_stale(&mut registry, now);
+
+    let username_lockout = remaining_lockout(&mut registry, &username_key(username), now);
+    let ip_lockout = ip.and_then(|ip| remaining_lockout(&mut registry, &ip_key(ip), now));
+
+    match (username_lockout, ip_lockout) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (Some(a), None) | (None, Some(a)) => Some(a),
+        (None, None) => None,
+    }
+}
+
+fn record_failure_for(registry: &mut HashMap<String, AttemptRecord>, key: String, now: OffsetDateTime) {
+    let record = registry.entry(key).or_default();
+    record.prune(now);
+    record.failures.push(now);
+
+    if record.failures.len() as u32 >= THRESHOLD {
+        let breaches = record.failures.len() as u32 / THRESHOLD;
+        let lockout = BASE_LOCKOUT * 2_i32.pow(breaches - 1);
+        record.locked_until = Some(now + lockout);
+    }
+}
+
+/// Records a failed login attempt against `username` and, if known, `ip`,
+/// applying exponential backoff once `THRESHOLD` failures have accumulated
+/// within `WINDOW` for either.
+pub fn record_failure(username: &str, ip: Option<&str>) {
+    let now = OffsetDateTime::now_utc();
+    let mut registry = registry().lock().unwrap();
+    record_failure_for(&mut registry, username_key(username), now);
+    if let Some(ip) = ip {
+        record_failure_for(&mut registry, ip_key(ip), now);
+    }
+}
+
+/// Clears `username`'s (and `ip`'s) failure history after a successful login.
+pub fn record_success(username: &str, ip: Option<&str>) {
+    let mut registry = registry().lock().unwrap();
+    registry.remove(&username_key(username));
+    if let Some(ip) = ip {
+        registry.remove(&ip_key(ip));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `REGISTRY` is a single process-wide static shared by every test in this
+    // module (and `cargo test` runs them concurrently by default), so each
+    // test uses its own username/IP that no other test touches rather than
+    // resetting shared state between tests.
+
+    #[test]
+    fn check_returns_none_with_no_recorded_attempts() {
+        assert_eq!(check("throttle-test-fresh-user", None), None);
+    }
+
+    #[test]
+    fn under_threshold_failures_do_not_lock() {
+        let username = "throttle-test-under-threshold";
+        for _ in 0..(THRESHOLD - 1) {
+            record_failure(username, None);
+        }
+        assert_eq!(check(username, None), None);
+    }
+
+    #[test]
+    fn threshold_failures_lock_for_base_lockout() {
+        let username = "throttle-test-at-threshold";
+        for _ in 0..THRESHOLD {
+            record_failure(username, None);
+        }
+        let remaining = check(username, None).expect("should be locked out");
+        assert!(remaining > Duration::ZERO);
+        assert!(remaining <= BASE_LOCKOUT);
+    }
+
+    #[test]
+    fn second_threshold_breach_doubles_the_lockout() {
+        // This test's lockout from the first breach needs to have already
+        // expired by the time the second breach is recorded, or the second
+        // breach's `locked_until` (now + 2x) would be compared against a
+        // `now` that's already inside the first lockout and the assertion
+        // below would still hold — but to keep the math unambiguous, record
+        // all 2x failures back to back and only check the final state.
+        let username = "throttle-test-double-breach";
+        for _ in 0..(THRESHOLD * 2) {
+            record_failure(username, None);
+        }
+        let remaining = check(username, None).expect("should be locked out");
+        // Second breach's lockout is `now + BASE_LOCKOUT * 2`, recorded after
+        // the first breach's `now + BASE_LOCKOUT` — so the remaining lockout
+        // observed here is noticeably more than a single `BASE_LOCKOUT`.
+        assert!(remaining > BASE_LOCKOUT);
+    }
+
+    #[test]
+    fn record_success_clears_an_active_lockout() {
+        let username = "throttle-test-success-clears";
+        for _ in 0..THRESHOLD {
+            record_failure(username, None);
+        }
+        assert!(check(username, None).is_some());
+
+        record_success(username, None);
+        assert_eq!(check(username, None), None);
+    }
+
+    #[test]
+    fn ip_lockout_applies_independently_of_username() {
+        let ip = "203.0.113.42";
+        for i in 0..THRESHOLD {
+            let username = format!("throttle-test-ip-lockout-{i}");
+            record_failure(&username, Some(ip));
+        }
+        // A brand-new username sharing the locked-out IP is still blocked.
+        assert!(check("throttle-test-ip-lockout-fresh-user", Some(ip)).is_some());
+    }
+}
@@ -0,0 +1,35 @@
+//! Pluggable outbound email delivery.
+//!
+//! [`Mailer`] is the extension point callers depend on instead of a concrete
+//! SMTP/SES client, e.g. `utils::password_reset::request_reset` takes `&dyn
+//! Mailer` rather than sending mail itself. [`LoggingMailer`] is the default
+//! implementation until a real provider is wired up — it just prints the
+//! message instead of delivering it.
+
+/// A single outbound email.
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[rocket::async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, email: Email) -> Result<(), String>;
+}
+
+/// Default `Mailer`: logs the message instead of delivering it. Swap in a
+/// real SES/SMTP-backed implementation for production.
+pub struct LoggingMailer;
+
+#[rocket::async_trait]
+impl Mailer for LoggingMailer {
+    async fn send(&self, email: Email) -> Result<(), String> {
+        println!(
+            "[mailer] to={} subject={}\n{}",
+            email.to, email.subject, email.body
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+//! OAuth2 access/refresh token issuance and rotation.
+//!
+//! Built on [`OauthToken`] rather than `Authentication`'s opaque session
+//! tokens: every grant issues an access/refresh *pair* sharing a `family_id`,
+//! so [`refresh`] can tell a legitimate rotation apart from reuse of a
+//! refresh token that was already rotated past — the standard signal that a
+//! refresh token was stolen and replayed. On reuse, [`refresh`] revokes every
+//! token in the family and returns `AuthenticationError::TokenReuseDetected`.
+//! Scope checking lives on the model itself, see `OauthToken::has_scope`.
+
+use sha2::{Digest, Sha256};
+use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::database::predicate::Condition;
+use crate::database::transaction::{with_transaction, DbConn};
+use crate::database::values::DatabaseValue;
+use crate::error::DatabaseError;
+use crate::models::authentication::AuthenticationError;
+use crate::models::oauth_token::{OauthToken, OauthTokenError};
+use crate::{
+    delete_resource_where_condition, delete_resource_where_fields, find_one_resource_where_fields,
+    insert_resource,
+};
+
+const ACCESS_TOKEN_TTL: Duration = Duration::hours(1);
+const REFRESH_TOKEN_TTL: Duration = Duration::days(30);
+
+/// Hashes a raw access/refresh token value with SHA-256 before it's stored or
+/// looked up, the same pattern `api::authentications::hash_refresh_token`
+/// uses for `Authentication.token` — these are high-entropy, single-use-ish
+/// bearer secrets rather than user-chosen passwords, so a fast hash is fine
+/// and lets lookups stay a plain indexed equality match. The raw value is
+/// only ever handed back to the caller at issuance/rotation time; from then
+/// on, only its hash exists anywhere in the database.
+pub fn hash_token_value(raw: &str) -> String {
+    format!("{:x}", Sha256::digest(raw.as_bytes()))
+}
+
+/// An issued access/refresh pair, returned from [`issue`] and [`refresh`].
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub access_expires_at: OffsetDateTime,
+    pub refresh_expires_at: OffsetDateTime,
+}
+
+fn format_timestamp(timestamp: OffsetDateTime) -> String {
+    timestamp.format(&Iso8601::DEFAULT).unwrap()
+}
+
+/// Inserts one `OauthToken` row, via `$conn` when given one so a rotation's
+/// archive-old/insert-new pair lands in the same transaction.
+async fn insert_token(
+    user_id: &str,
+    client_id: &str,
+    family_id: &str,
+    token_type: &str,
+    token_value: &str,
+    scope: &str,
+    expires_at: OffsetDateTime,
+    conn: Option<&DbConn>,
+) -> Result<OauthToken, DatabaseError> {
+    let params = vec![
+        ("user_id", DatabaseValue::String(user_id.to_string())),
+        ("client_id", DatabaseValue::String(client_id.to_string())),
+        ("family_id", DatabaseValue::String(family_id.to_string())),
+        ("token_type", DatabaseValue::String(token_type.to_string())),
+        (
+            "token_value",
+            DatabaseValue::String(hash_token_value(token_value)),
+        ),
+        ("scope", DatabaseValue::String(scope.to_string())),
+        (
+            "expires_at",
+            DatabaseValue::DateTime(format_timestamp(expires_at)),
+        ),
+    ];
+
+    match conn {
+        Some(conn) => insert_resource!(OauthToken, params, conn).await,
+        None => insert_resource!(OauthToken, params).await,
+    }
+}
+
+/// Issues a fresh access/refresh pair for a new `family_id`, via `$conn` when given one.
+async fn issue_pair(
+    user_id: &str,
+    client_id: &str,
+    scope: &str,
+    family_id: &str,
+    conn: Option<&DbConn>,
+) -> Result<TokenPair, DatabaseError> {
+    let now = OffsetDateTime::now_utc();
+    let access_expires_at = now + ACCESS_TOKEN_TTL;
+    let refresh_expires_at = now + REFRESH_TOKEN_TTL;
+    let access_token = Uuid::new_v4().to_string();
+    let refresh_token = Uuid::new_v4().to_string();
+
+    insert_token(
+        user_id,
+        client_id,
+        family_id,
+        "access",
+        &access_token,
+        scope,
+        access_expires_at,
+        conn,
+    )
+    .await?;
+    insert_token(
+        user_id,
+        client_id,
+        family_id,
+        "refresh",
+        &refresh_token,
+        scope,
+        refresh_expires_at,
+        conn,
+    )
+    .await?;
+
+    Ok(TokenPair {
+        access_token,
+        refresh_token,
+        access_expires_at,
+        refresh_expires_at,
+    })
+}
+
+/// Issues a brand-new access/refresh pair for a user, starting a new token family.
+pub async fn issue(user_id: &str, client_id: &str, scope: &str) -> Result<TokenPair, OauthTokenError> {
+    let family_id = Uuid::new_v4().to_string();
+    issue_pair(user_id, client_id, scope, &family_id, None)
+        .await
+        .map_err(|_| OauthTokenError::OauthTokenCreationFailed)
+}
+
+/// Redeems a refresh token for a new access/refresh pair, rotating the refresh
+/// token in the same transaction: the presented token is archived (soft-deleted)
+/// and its replacement keeps the same `family_id`.
+///
+/// If the presented token was already archived — i.e. it was already rotated
+/// away from, and this is a second presentation of it — the whole family is
+/// revoked and `AuthenticationError::TokenReuseDetected` is returned instead of
+/// a fresh pair.
+pub async fn refresh(presented_refresh_token: &str) -> Result<TokenPair, AuthenticationError> {
+    let lookup_params = vec![(
+        "token_value",
+        DatabaseValue::String(hash_token_value(presented_refresh_token)),
+    )];
+    // include_archived: true, since an already-rotated (archived) refresh
+    // token still needs to be found here to detect reuse below.
+    let token: OauthToken = find_one_resource_where_fields!(OauthToken, lookup_params, true)
+        .await
+        .map_err(|_| AuthenticationError::InvalidToken)?;
+
+    if token.token_type.as_deref() != Some("refresh") {
+        return Err(AuthenticationError::InvalidToken);
+    }
+
+    let family_id = token.family_id.clone().unwrap_or_default();
+
+    if token.archived_at.is_some() {
+        revoke_family(&family_id).await;
+        return Err(AuthenticationError::TokenReuseDetected);
+    }
+
+    if token
+        .expires_at
+        .map(|expires_at| expires_at < OffsetDateTime::now_utc())
+        .unwrap_or(true)
+    {
+        return Err(AuthenticationError::TokenExpired);
+    }
+
+    let token_id = token.id.clone().unwrap_or_default();
+    let user_id = token.user_id.clone().unwrap_or_default();
+    let client_id = token.client_id.clone().unwrap_or_default();
+    let scope = token.scope.clone().unwrap_or_default();
+
+    with_transaction(|conn| async move {
+        let revoke_params = vec![("id", DatabaseValue::String(token_id))];
+        delete_resource_where_fields!(OauthToken, revoke_params, conn).await?;
+        issue_pair(&user_id, &client_id, &scope, &family_id, Some(conn)).await
+    })
+    .await
+    .map_err(|_| AuthenticationError::InvalidToken)
+}
+
+/// Revokes every token — access and refresh alike — sharing `family_id`.
+/// Used when refresh-token reuse is detected, but also suitable for a
+/// "log out everywhere" action.
+pub async fn revoke_family(family_id: &str) {
+    let condition = Condition::pred("family_id", DatabaseValue::String(family_id.to_string()));
+    let _ = delete_resource_where_condition!(OauthToken, condition).await;
+}
+
+/// Revokes a single token by its value, e.g. an explicit logout.
+pub async fn revoke(token_value: &str) -> Result<(), OauthTokenError> {
+    let params = vec![(
+        "token_value",
+        DatabaseValue::String(hash_token_value(token_value)),
+    )];
+    delete_resource_where_fields!(OauthToken, params)
+        .await
+        .map_err(|_| OauthTokenError::OauthTokenDeletionFailed)
+}
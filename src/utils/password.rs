@@ -0,0 +1,51 @@
+//! Password hashing and verification.
+//!
+//! Replaces the old unsalted `Sha256::digest` + hex-equality scheme with
+//! Argon2id: [`hash_password`] stores a full PHC-format string
+//! (`$argon2id$v=19$m=...,t=...,p=...$salt$hash`), so the salt and cost
+//! parameters travel with the hash and a `user_password` column lookup can no
+//! longer be an equality WHERE clause — the caller has to fetch the user by
+//! username and check the candidate password with [`verify_password`]
+//! instead. [`is_legacy_hash`] flags the old 64-char hex digests so callers
+//! can detect them on login and transparently re-hash to Argon2 once the
+//! plaintext is in hand.
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use sha2::{Digest, Sha256};
+
+/// Hashes `plain` with Argon2id, using a fresh random salt, returning the
+/// full PHC-format encoded string for storage in `user_password`.
+pub fn hash_password(plain: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(plain.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+}
+
+/// Verifies `plain` against `stored`, a PHC-format hash produced by
+/// [`hash_password`]. Returns `false` (rather than an error) for anything
+/// that fails to parse as a PHC string, e.g. a legacy SHA-256 hex digest —
+/// callers that need to accept those should check [`is_legacy_hash`] first.
+pub fn verify_password(plain: &str, stored: &str) -> bool {
+    match PasswordHash::new(stored) {
+        Ok(parsed_hash) => Argon2::default()
+            .verify_password(plain.as_bytes(), &parsed_hash)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Reports whether `stored` looks like one of the old unsalted
+/// `Sha256::digest` hex digests rather than a PHC-format Argon2 hash.
+pub fn is_legacy_hash(stored: &str) -> bool {
+    stored.len() == 64 && stored.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Verifies `plain` against a legacy SHA-256 hex digest, the same way the
+/// old login/register/reset-password endpoints used to compare hashes.
+pub fn verify_legacy_hash(plain: &str, stored: &str) -> bool {
+    format!("{:x}", Sha256::digest(plain.as_bytes())) == stored
+}
@@ -0,0 +1,159 @@
+//! Email-driven password reset: a single-use, time-limited token delivered
+//! out of band via a pluggable `utils::mailer::Mailer`.
+//!
+//! This is a second recovery path alongside `utils::backup_codes` for
+//! `api::authentications::reset_password` — a backup code proves possession
+//! of something generated at registration time, a reset token proves control
+//! of the account right now. Only one reset token is ever live per user:
+//! requesting a new one overwrites the last instead of accumulating rows.
+
+use argon2::password_hash::rand_core::OsRng;
+use base32::Alphabet;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime};
+
+use crate::database::values::DatabaseValue;
+use crate::find_one_resource_where_fields;
+use crate::insert_resource;
+use crate::models::password_reset_token::{PasswordResetToken, PasswordResetTokenError};
+use crate::update_resource;
+use crate::utils::mailer::{Email, Mailer};
+
+/// How long a reset token is valid for before it must be requested again.
+const RESET_TOKEN_TTL: Duration = Duration::hours(1);
+
+/// Generates a reset token with 256 bits of CSPRNG entropy, base32-encoded
+/// (RFC 4648, no padding) the way the link/code embedded in the email
+/// expects it.
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    base32::encode(Alphabet::Rfc4648 { padding: false }, &bytes)
+}
+
+/// Hashes a reset token for storage/lookup, the same way
+/// `api::authentications::hash_refresh_token` hashes refresh tokens: already
+/// high-entropy CSPRNG output, so a deterministic SHA-256 digest is enough —
+/// no per-row salt needed the way a low-entropy password needs Argon2.
+pub fn hash_token(raw: &str) -> String {
+    format!("{:x}", Sha256::digest(raw.as_bytes()))
+}
+
+/// Compares two strings in constant time with respect to their contents, to
+/// avoid leaking a valid token hash byte-by-byte through timing.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Issues a fresh reset token for `user_id` and emails it to
+/// `recipient` via `mailer`. A user has at most one live token at a time, so
+/// an existing row is overwritten in place rather than inserting a second one
+/// alongside it.
+pub async fn request_reset(
+    user_id: &str,
+    recipient: &str,
+    mailer: &dyn Mailer,
+) -> Result<(), PasswordResetTokenError> {
+    let raw_token = generate_token();
+    let hashed_token = hash_token(&raw_token);
+    let expires_at = (OffsetDateTime::now_utc() + RESET_TOKEN_TTL)
+        .format(&Iso8601::DEFAULT)
+        .unwrap();
+
+    let lookup_params = vec![("user_id", DatabaseValue::String(user_id.to_string()))];
+    match find_one_resource_where_fields!(PasswordResetToken, lookup_params).await {
+        Ok(existing) => {
+            let id = existing.id.clone().unwrap_or_default();
+            update_resource!(
+                PasswordResetToken,
+                id,
+                vec![
+                    ("token", DatabaseValue::String(hashed_token)),
+                    ("used", DatabaseValue::Boolean(false.to_string())),
+                    ("expires_at", DatabaseValue::DateTime(expires_at)),
+                ]
+            )
+            .await
+            .map_err(|_| PasswordResetTokenError::TokenUpdateFailed)?;
+        }
+        Err(_) => {
+            insert_resource!(
+                PasswordResetToken,
+                vec![
+                    ("user_id", DatabaseValue::String(user_id.to_string())),
+                    ("token", DatabaseValue::String(hashed_token)),
+                    ("used", DatabaseValue::Boolean(false.to_string())),
+                    ("expires_at", DatabaseValue::DateTime(expires_at)),
+                ]
+            )
+            .await
+            .map_err(|_| PasswordResetTokenError::TokenCreationFailed)?;
+        }
+    }
+
+    let email = Email {
+        to: recipient.to_string(),
+        subject: "Reset your password".to_string(),
+        body: format!(
+            "Use this code to reset your password (expires in 1 hour): {}",
+            raw_token
+        ),
+    };
+    mailer
+        .send(email)
+        .await
+        .map_err(|_| PasswordResetTokenError::TokenCreationFailed)?;
+
+    Ok(())
+}
+
+/// Verifies `submitted_token` against `user_id`'s stored reset token and, on
+/// success, marks it consumed so it can't be redeemed a second time — the
+/// same "mark used on success" shape as `utils::backup_codes::verify_backup_code`.
+///
+/// The stored hash is looked up by `user_id` alone, never by the token value
+/// itself, and compared with [`constant_time_eq`] rather than folding the
+/// comparison into the `WHERE` clause.
+pub async fn verify_and_consume_reset_token(
+    user_id: &str,
+    submitted_token: &str,
+) -> Result<(), PasswordResetTokenError> {
+    let lookup_params = vec![("user_id", DatabaseValue::String(user_id.to_string()))];
+    let stored: PasswordResetToken =
+        find_one_resource_where_fields!(PasswordResetToken, lookup_params)
+            .await
+            .map_err(|_| PasswordResetTokenError::TokenNotFound)?;
+
+    let stored_hash = stored.token.clone().unwrap_or_default();
+    if !constant_time_eq(&hash_token(submitted_token), &stored_hash) {
+        return Err(PasswordResetTokenError::TokenNotFound);
+    }
+
+    if stored.used.unwrap_or(false) {
+        return Err(PasswordResetTokenError::TokenAlreadyUsed);
+    }
+
+    if stored
+        .expires_at
+        .map(|expires_at| expires_at < OffsetDateTime::now_utc())
+        .unwrap_or(true)
+    {
+        return Err(PasswordResetTokenError::TokenExpired);
+    }
+
+    let id = stored.id.clone().unwrap_or_default();
+    update_resource!(
+        PasswordResetToken,
+        id,
+        vec![("used", DatabaseValue::Boolean(true.to_string()))]
+    )
+    .await
+    .map_err(|_| PasswordResetTokenError::TokenUpdateFailed)?;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+//! Security-stamp based mass token revocation.
+//!
+//! Every user has a `security_stamp` column on the `users` table — an opaque
+//! random value embedded into each stateless JWT issued for them (see
+//! `utils::jwt::Claims::stamp`) and checked against their current stamp on
+//! every verification. [`rotate_security_stamp`] replaces that value, which
+//! instantly invalidates every token issued before the call — the way
+//! `VerifiedToken::from_raw` invalidates an opaque `Authentication` row by
+//! deleting it, but without touching a single session row. Call it whenever
+//! a user changes their password or their 2FA configuration.
+
+use crate::database::connection::get_connection;
+use crate::error::DatabaseError;
+use uuid::Uuid;
+
+/// Fetches `user_id`'s current `security_stamp` directly from the `users`
+/// table, rather than through `find_one_resource_where_fields!(User, ...)` —
+/// verifying a stateless JWT should cost one indexed scalar lookup, not a
+/// full-row fetch and `DatabaseResource` conversion.
+pub async fn get_security_stamp(user_id: &str) -> Result<Option<String>, DatabaseError> {
+    let pool = get_connection().await;
+    sqlx::query_scalar::<_, Option<String>>("SELECT security_stamp FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_optional(&pool)
+        .await
+        .map(Option::flatten)
+        .map_err(DatabaseError::from)
+}
+
+/// Rotates `user_id`'s security stamp to a fresh random value and returns it.
+///
+/// Every JWT issued before this call stops verifying the moment it returns —
+/// there's nothing to clean up individually. Opaque, database-backed tokens
+/// are unaffected by the stamp and still need their `Authentication` row
+/// removed directly, as before.
+pub async fn rotate_security_stamp(user_id: &str) -> Result<String, DatabaseError> {
+    let new_stamp = Uuid::new_v4().to_string();
+    let pool = get_connection().await;
+    sqlx::query("UPDATE users SET security_stamp = $1 WHERE id = $2")
+        .bind(&new_stamp)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .map_err(DatabaseError::from)?;
+    Ok(new_stamp)
+}
@@ -0,0 +1,117 @@
+//! RFC 6238 TOTP (Time-based One-Time Password) generation and verification.
+//!
+//! This is the primary second factor; `utils::backup_codes` is the recovery
+//! fallback for when a user's authenticator app isn't available.
+
+use base32::Alphabet;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+use time::OffsetDateTime;
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// The step size defined by RFC 6238's reference implementation.
+const STEP_SECONDS: i64 = 30;
+/// Number of digits in a generated code.
+const DIGITS: u32 = 6;
+/// How many steps of clock skew either side of "now" to accept.
+const SKEW_STEPS: i64 = 1;
+
+/// Generates a fresh, random per-user TOTP secret, base32-encoded (RFC 4648,
+/// no padding) the way authenticator apps expect it.
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 20];
+    rand::rng().fill_bytes(&mut bytes);
+    base32::encode(Alphabet::Rfc4648 { padding: false }, &bytes)
+}
+
+/// RFC 4226 HOTP: `HMAC-SHA1(secret, 8-byte big-endian counter)`, dynamically
+/// truncated to a `DIGITS`-digit code.
+fn hotp(secret_bytes: &[u8], counter: u64) -> u32 {
+    let mut mac =
+        HmacSha1::new_from_slice(secret_bytes).expect("HMAC accepts keys of any length");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let truncated = ((hash[offset] as u32 & 0x7f) << 24)
+        | ((hash[offset + 1] as u32) << 16)
+        | ((hash[offset + 2] as u32) << 8)
+        | (hash[offset + 3] as u32);
+
+    truncated % 10u32.pow(DIGITS)
+}
+
+/// Checks `code` against `secret_bytes` across `T-1`, `T`, `T+1` for the
+/// given `current_step` — the skew-tolerant comparison [`verify`] runs at
+/// the real current step, pulled out so tests can drive it at a fixed step
+/// instead of the wall clock.
+fn verify_at_step(secret_bytes: &[u8], code: &str, current_step: i64) -> bool {
+    (-SKEW_STEPS..=SKEW_STEPS).any(|skew| {
+        let step = (current_step + skew) as u64;
+        format!("{:0width$}", hotp(secret_bytes, step), width = DIGITS as usize) == code
+    })
+}
+
+/// Verifies `code` against `secret` (base32-encoded, as returned by
+/// [`generate_secret`]) at the current time.
+///
+/// Computes `T = floor((now - T0) / 30)` with `T0 = 0` (the Unix epoch) and
+/// accepts `T-1`, `T`, and `T+1` to tolerate clock skew between the server
+/// and the authenticator app. Returns `false` for a malformed secret rather
+/// than panicking.
+pub fn verify(secret: &str, code: &str) -> bool {
+    let Some(secret_bytes) = base32::decode(Alphabet::Rfc4648 { padding: false }, secret) else {
+        return false;
+    };
+    let current_step = OffsetDateTime::now_utc().unix_timestamp() / STEP_SECONDS;
+    verify_at_step(&secret_bytes, code, current_step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code_at(secret_bytes: &[u8], step: i64) -> String {
+        format!("{:0width$}", hotp(secret_bytes, step as u64), width = DIGITS as usize)
+    }
+
+    #[test]
+    fn accepts_the_current_step() {
+        let secret_bytes = b"test-secret-bytes".to_vec();
+        let code = code_at(&secret_bytes, 1000);
+        assert!(verify_at_step(&secret_bytes, &code, 1000));
+    }
+
+    #[test]
+    fn accepts_one_step_behind_and_ahead() {
+        let secret_bytes = b"test-secret-bytes".to_vec();
+        let code_behind = code_at(&secret_bytes, 999);
+        let code_ahead = code_at(&secret_bytes, 1001);
+        assert!(verify_at_step(&secret_bytes, &code_behind, 1000));
+        assert!(verify_at_step(&secret_bytes, &code_ahead, 1000));
+    }
+
+    #[test]
+    fn rejects_two_steps_behind_and_ahead() {
+        let secret_bytes = b"test-secret-bytes".to_vec();
+        let code_behind = code_at(&secret_bytes, 998);
+        let code_ahead = code_at(&secret_bytes, 1002);
+        assert!(!verify_at_step(&secret_bytes, &code_behind, 1000));
+        assert!(!verify_at_step(&secret_bytes, &code_ahead, 1000));
+    }
+
+    #[test]
+    fn rejects_a_code_from_a_different_secret() {
+        let secret_bytes = b"test-secret-bytes".to_vec();
+        let other_secret_bytes = b"a-totally-different-secret".to_vec();
+        let code = code_at(&other_secret_bytes, 1000);
+        assert!(!verify_at_step(&secret_bytes, &code, 1000));
+    }
+
+    #[test]
+    fn verify_rejects_a_malformed_secret() {
+        assert!(!verify("not valid base32!!", "123456"));
+    }
+}
@@ -1,44 +1,164 @@
-use ws::{Stream, WebSocket};
+use crate::api::token::{validate_token, RawToken, VerifiedToken};
+use crate::models::authentication::AuthenticationError;
+use rocket::futures::StreamExt;
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::Request;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use tokio::sync::broadcast;
+use ws::{Message, Stream, WebSocket};
 
-/// WebSocket handler for real-time communication
+/// The capacity of each per-user broadcast channel. Once a slow subscriber falls
+/// this far behind, it starts missing the oldest frames instead of blocking
+/// `push_alert` for everyone else — see `tokio::sync::broadcast::Sender`.
+const CHANNEL_CAPACITY: usize = 100;
+
+/// A single alert pushed to a user over their live WebSocket connections.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertMessage {
+    pub id: String,
+    pub title: String,
+    pub body: String,
+    /// Unix seconds; kept as a plain integer rather than `OffsetDateTime` so
+    /// this type needs no custom (de)serializer of its own.
+    pub created_at: i64,
+}
+
+/// Process-wide registry of `user_id -> broadcast::Sender<AlertMessage>`.
+///
+/// One channel per user, shared by every live socket that user has open.
+/// `push_alert` fans a message out to all of them; connecting sockets
+/// subscribe to (and lazily create) their user's channel.
+static REGISTRY: OnceLock<Mutex<HashMap<String, broadcast::Sender<AlertMessage>>>> =
+    OnceLock::new();
+
+fn registry() -> &'static Mutex<HashMap<String, broadcast::Sender<AlertMessage>>> {
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Subscribes to `user_id`'s alert channel, creating it if this is their first connection.
+fn subscribe(user_id: &str) -> broadcast::Receiver<AlertMessage> {
+    let mut registry = registry().lock().unwrap();
+    registry
+        .entry(user_id.to_string())
+        .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+        .subscribe()
+}
+
+/// Fans `alert` out to every live WebSocket connection `user_id` has open.
+///
+/// A no-op (not an error) if the user has no live connections right now —
+/// this mirrors `broadcast::Sender::send`, which only errors when there are
+/// zero receivers, and callers shouldn't have to care whether anyone's
+/// actually listening.
+pub fn push_alert(user_id: &str, alert: AlertMessage) {
+    let registry = registry().lock().unwrap();
+    if let Some(sender) = registry.get(user_id) {
+        let _ = sender.send(alert);
+    }
+}
+
+/// Request guard that authenticates a WebSocket upgrade.
 ///
-/// This endpoint establishes a WebSocket connection and streams messages between the client and server.
-/// It uses Rocket's streaming capabilities to handle WebSocket communication efficiently.
+/// Browsers can't set an `Authorization` header on a WebSocket handshake, so
+/// the token is read from the `Sec-WebSocket-Protocol` header (the standard
+/// workaround — clients connect with `new WebSocket(url, token)`), falling
+/// back to a `?token=` query parameter. The token is then validated exactly
+/// like any other request via `validate_token`, so it's accepted whether it's
+/// a stateless JWT or an opaque database-backed one. Failing either step
+/// rejects the upgrade with `401 Unauthorized` before the handler ever runs.
+struct WsUser(VerifiedToken);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for WsUser {
+    type Error = AuthenticationError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let raw_value = request
+            .headers()
+            .get_one("Sec-WebSocket-Protocol")
+            .map(|protocol| protocol.trim().to_string())
+            .or_else(|| {
+                request
+                    .query_value::<String>("token")
+                    .and_then(|value| value.ok())
+            });
+
+        let raw_value = match raw_value {
+            Some(value) if !value.is_empty() => value,
+            _ => {
+                return Outcome::Error((Status::Unauthorized, AuthenticationError::SessionNotFound))
+            }
+        };
+
+        match validate_token(RawToken { value: raw_value }).await {
+            Ok(token) => Outcome::Success(WsUser(token)),
+            Err(err) => Outcome::Error((Status::Unauthorized, err)),
+        }
+    }
+}
+
+/// WebSocket handler delivering live alerts to an authenticated user.
+///
+/// This endpoint authenticates the upgrade via [`WsUser`], then subscribes
+/// the connection to that user's entry in the process-wide alert registry
+/// (see [`push_alert`]) and forwards every alert broadcast on it as a JSON
+/// text frame for as long as the socket stays open. Client `Ping`/`Pong`
+/// frames are answered automatically by the underlying `ws` stream; a
+/// client `Close` frame ends the connection.
 ///
 /// # Route
 /// `GET /ws`
 ///
-/// # Returns
-/// A stream of WebSocket messages that can be:
-/// - Text messages
-/// - Binary messages
-/// - Ping/Pong frames
-/// - Close frames
-///
 /// # Example Client Usage
 /// ```javascript
-/// const ws = new WebSocket('ws://localhost:8000/ws');
+/// const ws = new WebSocket('ws://localhost:8000/ws', token);
 ///
 /// ws.onmessage = (event) => {
-///     console.log('Received:', event.data);
-/// };
-///
-/// ws.onopen = () => {
-///     ws.send('Hello Server!');
+///     const alert = JSON.parse(event.data);
+///     console.log('Alert:', alert);
 /// };
 /// ```
 ///
 /// # Error Handling
-/// - Connection errors are propagated through the stream
-/// - Invalid messages are handled gracefully
+/// - An upgrade with a missing, invalid, or expired token never reaches this handler
+/// - Connection errors from the underlying stream are propagated and end the connection
 /// - Client disconnections are handled automatically
 #[get("/")]
-pub fn ws_handler(ws: WebSocket) -> Stream!['static] {
-    println!("WebSocket connection established");
+pub fn ws_handler(ws: WebSocket, user: WsUser) -> Stream!['static] {
+    let user_id = user.0.user_id.clone();
+    let mut alerts = subscribe(&user_id);
+
     Stream! { ws =>
-        for await message in ws {
-            println!("Received message: {:?}", message);
-            yield message?;
+        let mut ws = ws;
+        loop {
+            tokio::select! {
+                message = ws.next() => {
+                    let Some(message) = message else { break };
+                    match message? {
+                        Message::Close(_) => break,
+                        // Ping/Pong are already handled transparently by the
+                        // underlying stream; nothing to forward for them.
+                        Message::Ping(_) | Message::Pong(_) => {}
+                        _ => {}
+                    }
+                }
+                alert = alerts.recv() => {
+                    match alert {
+                        Ok(alert) => {
+                            if let Ok(json) = serde_json::to_string(&alert) {
+                                yield Message::Text(json);
+                            }
+                        }
+                        // A lagging receiver just missed some frames; keep going.
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            }
         }
     }
 }
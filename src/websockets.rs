@@ -0,0 +1,81 @@
+//! Warp WebSocket change-feed endpoint.
+//!
+//! `main.rs` upgrades `/ws` connections here, after validating the token it
+//! extracted during the handshake (see `main::ws_auth`) — a browser can't
+//! set an `Authorization` header on a WebSocket handshake, so the token
+//! travels as the `Sec-WebSocket-Protocol` value or a `?token=` query param
+//! instead, the same workaround `websocket::WsUser` uses for the Rocket-side
+//! `/ws` endpoint. Delivery is scoped to that authenticated `user_id` — a
+//! connection never sees another account's events, no matter what it sends.
+//! A client can still narrow by resource type with a filter message right
+//! after connecting: `{"resourceTypes": ["User"]}`.
+
+use crate::database::change_feed::{self, ChangeEvent};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::sync::broadcast;
+use warp::ws::{Message, WebSocket};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubscriptionFilter {
+    #[serde(default)]
+    resource_types: Vec<String>,
+}
+
+impl SubscriptionFilter {
+    fn matches(&self, event: &ChangeEvent, user_id: &str) -> bool {
+        if event.user_id.as_deref() != Some(user_id) {
+            return false;
+        }
+        if !self.resource_types.is_empty() && !self.resource_types.contains(&event.resource) {
+            return false;
+        }
+        true
+    }
+}
+
+/// Streams the global change feed to a single connected client, scoped to
+/// `user_id` (the identity `main::ws_auth` already validated the connection's
+/// token against) and further narrowed by whatever `SubscriptionFilter` it
+/// sends as its first message (or unfiltered by resource type, if it sends
+/// anything else or nothing at all).
+pub async fn handle_connection(ws: WebSocket, user_id: String) {
+    let (mut outgoing, mut incoming) = ws.split();
+    let mut rx = change_feed::subscribe();
+
+    let filter = match incoming.next().await {
+        Some(Ok(message)) if message.is_text() => message
+            .to_str()
+            .ok()
+            .and_then(|text| serde_json::from_str::<SubscriptionFilter>(text).ok())
+            .unwrap_or_default(),
+        _ => SubscriptionFilter::default(),
+    };
+
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                match event {
+                    Ok(event) if filter.matches(&event, &user_id) => {
+                        if let Ok(json) = serde_json::to_string(&event) {
+                            if outgoing.send(Message::text(json)).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+            message = incoming.next() => {
+                match message {
+                    Some(Ok(message)) if message.is_close() => break,
+                    Some(Ok(_)) => {}
+                    Some(Err(_)) | None => break,
+                }
+            }
+        }
+    }
+}